@@ -0,0 +1,73 @@
+// Micro-benchmarks of the hot inner functions of the slower days, built only with `--features bench`. Each benchmark
+// times its function against generated inputs of increasing size, so it's clear how the function scales rather than
+// just how fast it is on the one real input.
+
+use criterion::Criterion;
+
+pub type BenchmarkFunction = fn(&mut Criterion);
+
+const BENCHMARKS: &[(u16, &[(usize, BenchmarkFunction)])] = &[(2022, crate::year2022::BENCHMARKS)];
+
+// Run the benchmarks for a year, or only those whose names (e.g. "day17/add_rock") match the filter regex.
+pub fn run(year: u16, filter: Option<&str>) {
+    let (_, benchmarks) = BENCHMARKS
+        .iter()
+        .find(|(registered, _)| *registered == year)
+        .unwrap_or_else(|| panic!("No benchmarks for {}.", year));
+    let mut criterion = Criterion::default();
+    if let Some(filter) = filter {
+        criterion = criterion.with_filter(filter);
+    }
+    for (_, benchmark) in benchmarks.iter() {
+        benchmark(&mut criterion);
+    }
+    criterion.final_summary();
+}
+
+// Generated inputs need to be the same on every run for the timings to be comparable, so rather than pulling in a
+// random number crate this is a plain linear congruential generator with a fixed seed.
+pub struct InputGenerator {
+    state: u64,
+}
+
+impl InputGenerator {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    // A number in 0..limit.
+    pub fn below(&mut self, limit: u64) -> u64 {
+        self.state = self
+            .state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.state >> 33) % limit
+    }
+
+    // A number in min..=max.
+    pub fn between(&mut self, min: i64, max: i64) -> i64 {
+        min + self.below((max - min + 1) as u64) as i64
+    }
+
+    pub fn choose<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.below(items.len() as u64) as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_input_generator() {
+        let mut generator = InputGenerator::new(1);
+        let rolls = (0..1000)
+            .map(|_| generator.between(-3, 3))
+            .collect::<Vec<i64>>();
+        assert!(rolls.iter().all(|roll| (-3..=3).contains(roll)));
+        assert!((-3..=3).all(|value| rolls.contains(&value)));
+
+        let mut replay = InputGenerator::new(1);
+        assert_eq!(replay.between(-3, 3), rolls[0]);
+    }
+}
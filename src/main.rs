@@ -1,92 +1,198 @@
-mod day00;
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day20;
-mod day21;
-mod day22;
-mod day23;
-mod day24;
-mod day25;
+#[cfg(feature = "bench")]
+mod benchmarks;
+mod scaffold;
+mod simulation;
+mod state;
+mod year2022;
 
 use std::env;
 
 type DayFunction = fn(&str) -> (String, String);
-const DAY_FUNCTIONS: [DayFunction; 26] = [
-    day00::day00,
-    day01::day01,
-    day02::day02,
-    day03::day03,
-    day04::day04,
-    day05::day05,
-    day06::day06,
-    day07::day07,
-    day08::day08,
-    day09::day09,
-    day10::day10,
-    day11::day11,
-    day12::day12,
-    day13::day13,
-    day14::day14,
-    day15::day15,
-    day16::day16,
-    day17::day17,
-    day18::day18,
-    day19::day19,
-    day20::day20,
-    day21::day21,
-    day22::day22,
-    day23::day23,
-    day24::day24,
-    day25::day25,
-];
+type SimulationFunction = fn(&str, u8) -> Box<dyn simulation::Simulation>;
+type ReportFunction = fn(&str, &[String]) -> String;
+
+// Each year lives in its own module (src/yearXXXX) and registers the days it has solutions for. Inputs for a year are
+// read from inputs/<year>/<day>, and known answers (if any) from answers/<year>/<day>.
+struct Year {
+    year: u16,
+    days: &'static [(usize, DayFunction)],
+    simulations: &'static [(usize, SimulationFunction)],
+    reports: &'static [(usize, ReportFunction)],
+}
+
+const YEARS: &[Year] = &[Year {
+    year: 2022,
+    days: year2022::DAYS,
+    simulations: year2022::SIMULATIONS,
+    reports: year2022::REPORTS,
+}];
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    let min_day: usize;
-    let max_day: usize;
-    if args.len() < 2 {
-        min_day = 1;
-        max_day = DAY_FUNCTIONS.len() - 1;
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let year_arg = take_year_arg(&mut args);
+    if args.first().map(String::as_str) == Some("new") {
+        new_day(year_arg, &args[1..]);
+        return;
+    }
+    let year = select_year(year_arg);
+    if args.first().map(String::as_str) == Some("debug") {
+        debug_day(year, &args[1..]);
+        return;
+    }
+    if args.first().map(String::as_str) == Some("report") {
+        report_day(year, &args[1..]);
+        return;
+    }
+    if args.first().map(String::as_str) == Some("bench") {
+        run_benchmarks(year, &args[1..]);
+        return;
+    }
+
+    let days: Vec<&(usize, DayFunction)> = if args.is_empty() {
+        // Day 0 is the template example, so only run it when explicitly asked.
+        year.days.iter().filter(|(day, _)| *day != 0).collect()
     } else {
-        min_day = args[1]
+        let day = args[0]
             .parse::<usize>()
             .expect("Please provide the day number as an integer.");
-        if min_day > DAY_FUNCTIONS.len() {
-            panic!("Invalid day specified.");
-        }
-        max_day = min_day;
-    }
+        vec![year
+            .days
+            .iter()
+            .find(|(registered_day, _)| *registered_day == day)
+            .unwrap_or_else(|| panic!("Invalid day specified for {}.", year.year))]
+    };
 
-    for day in min_day..=max_day {
+    for (day, day_function) in days {
         println!("Day {}", day);
-        let input_lines = load_input(day);
+        let input_lines = load_input(year.year, *day);
         let start_time = std::time::Instant::now();
-        let (part1, part2) = DAY_FUNCTIONS[day](&input_lines);
+        let (part1, part2) = day_function(&input_lines);
         let elapsed = start_time.elapsed().as_micros();
         println!("Part 1: {}\nPart 2: {}", part1, part2);
+        if let Some(answers) = load_answers(year.year, *day) {
+            check_answers(&answers, &part1, &part2);
+        }
         println!("{}.{:03}ms", elapsed / 1000, elapsed % 1000);
         println!("----------");
     }
+}
 
-    pub fn load_input(day: usize) -> String {
-        std::fs::read_to_string(format!("{}/{}", "inputs", day))
-            .expect("Can't open/read input file")
+// Pull `--year <year>` out of the argument list, leaving any remaining arguments in place.
+fn take_year_arg(args: &mut Vec<String>) -> Option<u16> {
+    let flag_idx = args.iter().position(|arg| arg == "--year")?;
+    args.remove(flag_idx);
+    if flag_idx >= args.len() {
+        panic!("--year needs a year to follow it.");
+    }
+    let year = args.remove(flag_idx);
+    Some(
+        year.parse::<u16>()
+            .unwrap_or_else(|_| panic!("Expected a year, not {}", year)),
+    )
+}
+
+// `new <day>` sets up the files for a day, in a new year if the year given with `--year` doesn't exist yet.
+fn new_day(year: Option<u16>, args: &[String]) {
+    let year = year.unwrap_or_else(|| select_year(None).year);
+    let day = args
+        .first()
+        .expect("Please provide the day number to create.")
+        .parse::<usize>()
+        .expect("Please provide the day number as an integer.");
+    match scaffold::new_day(year, day) {
+        Ok(written) => {
+            for path in written {
+                println!("Created {}", path);
+            }
+        }
+        Err(error) => panic!("Couldn't create day {} of {}: {}", day, year, error),
+    }
+}
+
+// `debug <day> [part]` steps through a day's simulation interactively.
+fn debug_day(year: &Year, args: &[String]) {
+    let day = args
+        .first()
+        .expect("Please provide the day number to debug.")
+        .parse::<usize>()
+        .expect("Please provide the day number as an integer.");
+    let part = args.get(1).map_or(1, |part| {
+        part.parse::<u8>()
+            .expect("Please provide the part number as an integer.")
+    });
+    let (_, simulation_function) = year
+        .simulations
+        .iter()
+        .find(|(registered_day, _)| *registered_day == day)
+        .unwrap_or_else(|| panic!("Day {} of {} has no simulation to debug.", day, year.year));
+    let mut simulation = simulation_function(&load_input(year.year, day), part);
+    simulation::repl(
+        simulation.as_mut(),
+        std::io::stdin().lock(),
+        std::io::stdout(),
+    );
+}
+
+// `report <day> [options]` prints a day's extra analysis of its input. What options there are depends on the day.
+fn report_day(year: &Year, args: &[String]) {
+    let day = args
+        .first()
+        .expect("Please provide the day number to report on.")
+        .parse::<usize>()
+        .expect("Please provide the day number as an integer.");
+    let (_, report_function) = year
+        .reports
+        .iter()
+        .find(|(registered_day, _)| *registered_day == day)
+        .unwrap_or_else(|| panic!("Day {} of {} has no report.", day, year.year));
+    print!(
+        "{}",
+        report_function(&load_input(year.year, day), &args[1..])
+    );
+}
+
+// `bench [filter]` times the hot inner functions of the slower days against generated inputs. The filter is a regex
+// on the benchmark names, e.g. `day17` or `add_rock`.
+#[cfg(feature = "bench")]
+fn run_benchmarks(year: &Year, args: &[String]) {
+    benchmarks::run(year.year, args.first().map(String::as_str));
+}
+
+#[cfg(not(feature = "bench"))]
+fn run_benchmarks(_year: &Year, _args: &[String]) {
+    panic!("Benchmarks need building with `--features bench` (and ideally `--release`).");
+}
+
+// Default to the most recent year we have solutions for.
+fn select_year(year: Option<u16>) -> &'static Year {
+    match year {
+        Some(year) => YEARS
+            .iter()
+            .find(|registered| registered.year == year)
+            .unwrap_or_else(|| panic!("No solutions for {}.", year)),
+        None => YEARS.iter().max_by_key(|year| year.year).unwrap(),
+    }
+}
+
+pub fn load_input(year: u16, day: usize) -> String {
+    std::fs::read_to_string(format!("inputs/{}/{}", year, day)).expect("Can't open/read input file")
+}
+
+// Answers files hold the part 1 answer on the first line and part 2 on the second. Part 2 answers that span multiple
+// lines (e.g. drawn screens) take up the rest of the file.
+pub fn load_answers(year: u16, day: usize) -> Option<(String, String)> {
+    let answers = std::fs::read_to_string(format!("answers/{}/{}", year, day)).ok()?;
+    let (part1, part2) = answers.split_once('\n').unwrap_or((&answers, ""));
+    Some((part1.trim_end().to_string(), part2.trim_end().to_string()))
+}
+
+fn check_answers(answers: &(String, String), part1: &str, part2: &str) {
+    for (part, expected, actual) in [(1, &answers.0, part1), (2, &answers.1, part2)] {
+        if !expected.is_empty() && expected != actual.trim_end() {
+            println!(
+                "Part {} doesn't match the stored answer: {}",
+                part, expected
+            );
+        }
     }
 }
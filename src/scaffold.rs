@@ -0,0 +1,358 @@
+// Generates the boilerplate for a new day: the solution module (from the template below), empty input and example
+// files, and the registration of the day (and the year, if it's a new one) with the runner.
+
+use std::fs;
+use std::path::Path;
+
+const DAY_TEMPLATE: &str = "pub fn day{NN}(input_lines: &str) -> (String, String) {
+    let answer1 = input_lines.lines().count();
+    let answer2 = 0;
+    (format!(\"{}\", answer1), format!(\"{}\", answer2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Paste the puzzle's example into examples/{YEAR}/{N} and swap in the expected answers.
+    const EXAMPLE: &str = include_str!(\"../../examples/{YEAR}/{N}\");
+
+    #[test]
+    fn check_day{NN}_part1_case1() {
+        assert_eq!(day{NN}(EXAMPLE).0, \"0\".to_string())
+    }
+
+    #[test]
+    fn check_day{NN}_part2_case1() {
+        assert_eq!(day{NN}(EXAMPLE).1, \"0\".to_string())
+    }
+
+    #[test]
+    fn check_day{NN}_both_case1() {
+        assert_eq!(day{NN}(EXAMPLE), (\"0\".to_string(), \"0\".to_string()))
+    }
+}
+";
+
+const YEAR_TEMPLATE: &str = "use crate::{DayFunction, ReportFunction, SimulationFunction};
+
+// One day per line, so `cargo run -- new` can add to the list.
+#[rustfmt::skip]
+pub const DAYS: &[(usize, DayFunction)] = &[
+];
+
+// Days that can be stepped through with `cargo run -- debug <day>`.
+pub const SIMULATIONS: &[(usize, SimulationFunction)] = &[];
+
+// Days with extra analysis of their input, shown with `cargo run -- report <day> [options]`.
+pub const REPORTS: &[(usize, ReportFunction)] = &[];
+";
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    InvalidDay(usize),
+    AlreadyExists(String),
+    BadRegistration(String),
+    Io(std::io::Error),
+}
+
+impl std::fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ScaffoldError::InvalidDay(day) => write!(f, "{} isn't a day of Advent", day),
+            ScaffoldError::AlreadyExists(existing) => write!(f, "{} already exists", existing),
+            ScaffoldError::BadRegistration(reason) => write!(f, "can't register day: {}", reason),
+            ScaffoldError::Io(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl From<std::io::Error> for ScaffoldError {
+    fn from(error: std::io::Error) -> Self {
+        ScaffoldError::Io(error)
+    }
+}
+
+// Create everything needed to start on a new day, returning the paths of the files that were written. Nothing is
+// written if the day's module already exists or the day is already registered.
+pub fn new_day(year: u16, day: usize) -> Result<Vec<String>, ScaffoldError> {
+    if day > 25 {
+        return Err(ScaffoldError::InvalidDay(day));
+    }
+    let year_dir = format!("src/year{}", year);
+    let module_path = format!("{}/day{:02}.rs", year_dir, day);
+    let year_module_path = format!("{}/mod.rs", year_dir);
+    if Path::new(&module_path).exists() {
+        return Err(ScaffoldError::AlreadyExists(module_path));
+    }
+
+    // Work out all the edits before touching the disk, so a failure leaves nothing half-done.
+    let new_year = !Path::new(&year_module_path).exists();
+    let year_module = if new_year {
+        YEAR_TEMPLATE.to_string()
+    } else {
+        fs::read_to_string(&year_module_path)?
+    };
+    let year_module = register_day(&year_module, day)?;
+    let main = if new_year {
+        Some(register_year(&fs::read_to_string("src/main.rs")?, year)?)
+    } else {
+        None
+    };
+
+    let mut written = vec![];
+    fs::create_dir_all(&year_dir)?;
+    fs::write(&module_path, render_day(year, day))?;
+    written.push(module_path);
+    fs::write(&year_module_path, year_module)?;
+    written.push(year_module_path);
+    if let Some(main) = main {
+        fs::write("src/main.rs", main)?;
+        written.push("src/main.rs".to_string());
+    }
+    // Inputs and examples may already have been saved before starting on the code, so keep any that exist.
+    for data_dir in ["inputs", "examples"] {
+        let data_path = format!("{}/{}/{}", data_dir, year, day);
+        if !Path::new(&data_path).exists() {
+            fs::create_dir_all(format!("{}/{}", data_dir, year))?;
+            fs::write(&data_path, "")?;
+            written.push(data_path);
+        }
+    }
+    Ok(written)
+}
+
+pub fn render_day(year: u16, day: usize) -> String {
+    DAY_TEMPLATE
+        .replace("{NN}", &format!("{:02}", day))
+        .replace("{N}", &day.to_string())
+        .replace("{YEAR}", &year.to_string())
+}
+
+// Add the `mod` declaration and `DAYS` entry for the day to a year module, keeping both in day order.
+pub fn register_day(year_module: &str, day: usize) -> Result<String, ScaffoldError> {
+    let mod_line = format!("mod day{:02};", day);
+    let days_line = format!("    ({}, day{:02}::day{:02}),", day, day, day);
+    let mut lines: Vec<String> = year_module.lines().map(|line| line.to_string()).collect();
+    if lines
+        .iter()
+        .any(|line| line.trim_start_matches("pub ") == mod_line)
+    {
+        return Err(ScaffoldError::AlreadyExists(format!("day {}", day)));
+    }
+
+    let days_start = lines
+        .iter()
+        .position(|line| line.starts_with("pub const DAYS"))
+        .ok_or_else(|| ScaffoldError::BadRegistration("no DAYS list found".to_string()))?;
+    let days_end = days_start
+        + lines[days_start..]
+            .iter()
+            .position(|line| line == "];")
+            .ok_or_else(|| ScaffoldError::BadRegistration("DAYS list isn't closed".to_string()))?;
+    let days_insert = (days_start + 1..days_end)
+        .find(|&idx| registered_day(&lines[idx]).is_some_and(|other| other > day))
+        .unwrap_or(days_end);
+    lines.insert(days_insert, days_line);
+
+    let mod_lines: Vec<usize> = (0..days_start)
+        .filter(|&idx| lines[idx].trim_start_matches("pub ").starts_with("mod day"))
+        .collect();
+    let mod_insert = mod_lines
+        .iter()
+        .copied()
+        .find(|&idx| lines[idx].trim_start_matches("pub ") > mod_line.as_str())
+        .or_else(|| mod_lines.last().map(|idx| idx + 1));
+    match mod_insert {
+        Some(idx) => lines.insert(idx, mod_line),
+        None => {
+            lines.insert(0, mod_line);
+            lines.insert(1, String::new());
+        }
+    }
+    Ok(lines.join("\n") + "\n")
+}
+
+fn registered_day(days_line: &str) -> Option<usize> {
+    days_line
+        .trim()
+        .strip_prefix('(')?
+        .split_once(',')?
+        .0
+        .parse::<usize>()
+        .ok()
+}
+
+// Add the `mod` declaration and `YEARS` entry for a new year to main.rs, rewriting the `YEARS` list in year order.
+pub fn register_year(main: &str, year: u16) -> Result<String, ScaffoldError> {
+    let year_regex = regex::Regex::new(r"year: (\d+),\s*days: year\d+::DAYS").unwrap();
+    let years_start = main
+        .find("const YEARS")
+        .ok_or_else(|| ScaffoldError::BadRegistration("no YEARS list found".to_string()))?;
+    let years_end = years_start
+        + main[years_start..]
+            .find("];")
+            .ok_or_else(|| ScaffoldError::BadRegistration("YEARS list isn't closed".to_string()))?
+        + 2;
+
+    let mut years: Vec<u16> = year_regex
+        .captures_iter(&main[years_start..years_end])
+        .map(|registered| registered[1].parse::<u16>().unwrap())
+        .collect();
+    if years.contains(&year) {
+        return Err(ScaffoldError::AlreadyExists(format!("year {}", year)));
+    }
+    years.push(year);
+    years.sort_unstable();
+    let years_list = years
+        .iter()
+        .map(|year| {
+            format!(
+                "    Year {{\n        year: {},\n        days: year{}::DAYS,\n        simulations: year{}::SIMULATIONS,\n        reports: year{}::REPORTS,\n    }},\n",
+                year, year, year, year
+            )
+        })
+        .collect::<String>();
+    let main = format!(
+        "{}const YEARS: &[Year] = &[\n{}];{}",
+        &main[..years_start],
+        years_list,
+        &main[years_end..]
+    );
+
+    let mod_line = format!("mod year{};", year);
+    let mut lines: Vec<&str> = main.lines().collect();
+    let mod_insert = lines
+        .iter()
+        .position(|line| line.starts_with("mod year") && *line > mod_line.as_str())
+        .or_else(|| {
+            lines
+                .iter()
+                .rposition(|line| line.starts_with("mod "))
+                .map(|idx| idx + 1)
+        })
+        .unwrap_or(0);
+    lines.insert(mod_insert, &mod_line);
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_render_day() {
+        let module = render_day(2023, 7);
+        assert!(module.starts_with("pub fn day07(input_lines: &str)"));
+        assert!(module.contains("fn check_day07_both_case1()"));
+        assert!(module.contains("include_str!(\"../../examples/2023/7\")"));
+    }
+
+    #[test]
+    fn check_register_day() {
+        assert_eq!(
+            register_day(
+                "mod day01;
+mod day05;
+
+use crate::DayFunction;
+
+pub const DAYS: &[(usize, DayFunction)] = &[
+    (1, day01::day01),
+    (5, day05::day05),
+];
+",
+                3
+            )
+            .unwrap(),
+            "mod day01;
+mod day03;
+mod day05;
+
+use crate::DayFunction;
+
+pub const DAYS: &[(usize, DayFunction)] = &[
+    (1, day01::day01),
+    (3, day03::day03),
+    (5, day05::day05),
+];
+"
+        )
+    }
+
+    #[test]
+    fn check_register_day_new_year() {
+        assert_eq!(
+            register_day(YEAR_TEMPLATE, 1).unwrap(),
+            "mod day01;
+
+use crate::{DayFunction, ReportFunction, SimulationFunction};
+
+// One day per line, so `cargo run -- new` can add to the list.
+#[rustfmt::skip]
+pub const DAYS: &[(usize, DayFunction)] = &[
+    (1, day01::day01),
+];
+
+// Days that can be stepped through with `cargo run -- debug <day>`.
+pub const SIMULATIONS: &[(usize, SimulationFunction)] = &[];
+
+// Days with extra analysis of their input, shown with `cargo run -- report <day> [options]`.
+pub const REPORTS: &[(usize, ReportFunction)] = &[];
+"
+        )
+    }
+
+    #[test]
+    fn check_register_day_refuses_duplicate() {
+        assert!(matches!(
+            register_day(&register_day(YEAR_TEMPLATE, 1).unwrap(), 1),
+            Err(ScaffoldError::AlreadyExists(_))
+        ))
+    }
+
+    #[test]
+    fn check_register_year() {
+        assert_eq!(
+            register_year(
+                "mod year2022;
+
+use std::env;
+
+const YEARS: &[Year] = &[Year {
+    year: 2022,
+    days: year2022::DAYS,
+    simulations: year2022::SIMULATIONS,
+    reports: year2022::REPORTS,
+}];
+
+fn main() {}
+",
+                2021
+            )
+            .unwrap(),
+            "mod year2021;
+mod year2022;
+
+use std::env;
+
+const YEARS: &[Year] = &[
+    Year {
+        year: 2021,
+        days: year2021::DAYS,
+        simulations: year2021::SIMULATIONS,
+        reports: year2021::REPORTS,
+    },
+    Year {
+        year: 2022,
+        days: year2022::DAYS,
+        simulations: year2022::SIMULATIONS,
+        reports: year2022::REPORTS,
+    },
+];
+
+fn main() {}
+"
+        )
+    }
+}
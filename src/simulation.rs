@@ -0,0 +1,303 @@
+// A common interface for the puzzles that are step-by-step simulations, so they can be driven and watched one step at
+// a time rather than by sprinkling prints through the solutions. `repl` gives an interactive debugger over any of them.
+
+use std::io::{BufRead, Write};
+
+use crate::state::StateError;
+
+pub trait Simulation {
+    // Advance the simulation by one step (a rock, a grain of sand, a round...). Returns false, having changed nothing,
+    // once the simulation has nothing more to do.
+    fn step(&mut self) -> bool;
+
+    // A picture of the current state, in the style of the puzzle's own diagrams.
+    fn snapshot(&self) -> String;
+
+    // Named values describing the current state, e.g. the round count or the tower height.
+    fn inspect(&self) -> Vec<(String, String)>;
+
+    // The full state as JSON, for simulations that can be saved and resumed later.
+    fn save_state(&self) -> Result<String, StateError> {
+        Err(StateError::Unsupported)
+    }
+
+    // Replace the current state with one previously produced by save_state.
+    fn load_state(&mut self, _json: &str) -> Result<(), StateError> {
+        Err(StateError::Unsupported)
+    }
+}
+
+// Step the simulation until the predicate holds, returning the number of steps that took. Gives up (returning the
+// number of steps taken as an error) if the simulation finishes or max_steps pass first.
+pub fn run_until<S: Simulation + ?Sized>(
+    simulation: &mut S,
+    mut predicate: impl FnMut(&S) -> bool,
+    max_steps: usize,
+) -> Result<usize, usize> {
+    let mut steps = 0;
+    loop {
+        if predicate(simulation) {
+            return Ok(steps);
+        }
+        if steps == max_steps || !simulation.step() {
+            return Err(steps);
+        }
+        steps += 1;
+    }
+}
+
+// Compares one of the simulation's inspected values against a constant. Numbers are compared numerically, anything
+// else as text (for which only `=` and `!=` make sense).
+#[derive(Debug, PartialEq)]
+pub struct Condition {
+    name: String,
+    comparison: Comparison,
+    value: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl Condition {
+    pub fn from_str(condition: &str) -> Option<Self> {
+        let mut parts = condition.split_whitespace();
+        let (name, comparison, value) = (parts.next()?, parts.next()?, parts.next()?);
+        if parts.next().is_some() {
+            return None;
+        }
+        let comparison = match comparison {
+            "=" | "==" => Comparison::Equal,
+            "!=" => Comparison::NotEqual,
+            "<" => Comparison::Less,
+            "<=" => Comparison::LessOrEqual,
+            ">" => Comparison::Greater,
+            ">=" => Comparison::GreaterOrEqual,
+            _ => return None,
+        };
+        Some(Self {
+            name: name.to_string(),
+            comparison,
+            value: value.to_string(),
+        })
+    }
+
+    pub fn holds<S: Simulation + ?Sized>(&self, simulation: &S) -> bool {
+        let Some((_, current)) = simulation
+            .inspect()
+            .into_iter()
+            .find(|(name, _)| *name == self.name)
+        else {
+            return false;
+        };
+        let ordering = match (current.parse::<f64>(), self.value.parse::<f64>()) {
+            (Ok(current), Ok(value)) => current.partial_cmp(&value),
+            _ => Some(current.as_str().cmp(self.value.as_str())),
+        };
+        let Some(ordering) = ordering else {
+            return false;
+        };
+        match self.comparison {
+            Comparison::Equal => ordering.is_eq(),
+            Comparison::NotEqual => ordering.is_ne(),
+            Comparison::Less => ordering.is_lt(),
+            Comparison::LessOrEqual => ordering.is_le(),
+            Comparison::Greater => ordering.is_gt(),
+            Comparison::GreaterOrEqual => ordering.is_ge(),
+        }
+    }
+}
+
+// The most steps `run` and `until` will take before handing back control, as some simulations never finish.
+const MAX_RUN_STEPS: usize = 100000;
+
+const REPL_HELP: &str = "Commands:
+  step [n]            Advance n steps (default 1). An empty line also steps once.
+  run                 Step until the simulation finishes (or 100000 steps pass).
+  until <name> <op> <value>
+                      Step until an inspected value satisfies the comparison (=, !=, <, <=, >, >=).
+  show                Print a snapshot of the current state.
+  inspect             List the inspected values.
+  save <path>         Save the current state to a JSON file.
+  load <path>         Resume from a state saved earlier.
+  help                Show this message.
+  quit                Leave the debugger.";
+
+// Drive a simulation interactively, reading commands from input and writing to output.
+pub fn repl(simulation: &mut dyn Simulation, input: impl BufRead, mut output: impl Write) {
+    let mut steps_taken = 0;
+    writeln!(output, "{}", REPL_HELP).unwrap();
+    write!(output, "> ").unwrap();
+    output.flush().unwrap();
+    for line in input.lines() {
+        let line = line.expect("Couldn't read command");
+        let (command, args) = line.trim().split_once(' ').unwrap_or((line.trim(), ""));
+        let report = match command {
+            "" | "s" | "step" => match args.trim() {
+                "" => Ok(1),
+                count => count.parse::<usize>().map_err(|_| "Expected a step count"),
+            }
+            .map(|count| {
+                let stepped = step_many(simulation, count);
+                steps_taken += stepped;
+                format!("Took {} step(s), {} in total", stepped, steps_taken)
+            }),
+            "r" | "run" => {
+                let stepped = step_many(simulation, MAX_RUN_STEPS);
+                steps_taken += stepped;
+                Ok(format!(
+                    "Took {} step(s), {} in total",
+                    stepped, steps_taken
+                ))
+            }
+            "u" | "until" => Condition::from_str(args)
+                .ok_or("Expected a condition like `rounds >= 10`")
+                .map(|condition| {
+                    match run_until(&mut *simulation, |sim| condition.holds(sim), MAX_RUN_STEPS) {
+                        Ok(stepped) => {
+                            steps_taken += stepped;
+                            format!("Condition met after {} step(s)", stepped)
+                        }
+                        Err(stepped) => {
+                            steps_taken += stepped;
+                            format!(
+                                "Finished or ran out of steps before condition was met, after {} step(s)",
+                                stepped
+                            )
+                        }
+                    }
+                }),
+            "show" => Ok(simulation.snapshot()),
+            "i" | "inspect" => Ok(simulation
+                .inspect()
+                .iter()
+                .map(|(name, value)| format!("{}: {}", name, value))
+                .collect::<Vec<String>>()
+                .join("\n")),
+            "save" => Ok(simulation
+                .save_state()
+                .and_then(|json| Ok(std::fs::write(args.trim(), json)?))
+                .map_or_else(
+                    |error| format!("Couldn't save: {}", error),
+                    |_| "Saved".to_string(),
+                )),
+            "load" => Ok(std::fs::read_to_string(args.trim())
+                .map_err(StateError::from)
+                .and_then(|json| simulation.load_state(&json))
+                .map_or_else(
+                    |error| format!("Couldn't load: {}", error),
+                    |_| "Loaded".to_string(),
+                )),
+            "h" | "help" => Ok(REPL_HELP.to_string()),
+            "q" | "quit" => break,
+            _ => Err("Unknown command, try `help`"),
+        };
+        match report {
+            Ok(report) => writeln!(output, "{}", report).unwrap(),
+            Err(error) => writeln!(output, "{}", error).unwrap(),
+        }
+        write!(output, "> ").unwrap();
+        output.flush().unwrap();
+    }
+}
+
+// Take up to count steps, stopping early if the simulation finishes. Returns the number of steps taken.
+fn step_many(simulation: &mut dyn Simulation, count: usize) -> usize {
+    let mut stepped = 0;
+    while stepped < count && simulation.step() {
+        stepped += 1;
+    }
+    stepped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Counter {
+        count: u32,
+        limit: u32,
+    }
+
+    impl Simulation for Counter {
+        fn step(&mut self) -> bool {
+            if self.count == self.limit {
+                return false;
+            }
+            self.count += 1;
+            true
+        }
+
+        fn snapshot(&self) -> String {
+            "#".repeat(self.count as usize)
+        }
+
+        fn inspect(&self) -> Vec<(String, String)> {
+            vec![("count".to_string(), self.count.to_string())]
+        }
+    }
+
+    #[test]
+    fn check_run_until() {
+        let mut counter = Counter {
+            count: 0,
+            limit: 10,
+        };
+        assert_eq!(run_until(&mut counter, |c| c.count == 4, 100), Ok(4));
+        assert_eq!(run_until(&mut counter, |c| c.count == 4, 100), Ok(0));
+        assert_eq!(run_until(&mut counter, |c| c.count == 20, 100), Err(6));
+        assert_eq!(counter.count, 10);
+    }
+
+    #[test]
+    fn check_condition() {
+        let counter = Counter {
+            count: 7,
+            limit: 10,
+        };
+        assert!(Condition::from_str("count >= 7").unwrap().holds(&counter));
+        assert!(Condition::from_str("count < 10").unwrap().holds(&counter));
+        assert!(!Condition::from_str("count != 7").unwrap().holds(&counter));
+        assert!(!Condition::from_str("missing = 7").unwrap().holds(&counter));
+        assert_eq!(Condition::from_str("count ~ 7"), None);
+    }
+
+    #[test]
+    fn check_repl() {
+        let mut counter = Counter {
+            count: 0,
+            limit: 10,
+        };
+        let mut output = Vec::new();
+        repl(
+            &mut counter,
+            "step 3\n\nuntil count = 6\nshow\ninspect\nquit\nstep\n".as_bytes(),
+            &mut output,
+        );
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("Took 3 step(s), 3 in total"));
+        assert!(output.contains("Took 1 step(s), 4 in total"));
+        assert!(output.contains("Condition met after 2 step(s)"));
+        assert!(output.contains("######\n"));
+        assert!(output.contains("count: 6"));
+        assert_eq!(counter.count, 6);
+
+        // Steps are counted when the condition isn't met, but not once the simulation has finished.
+        let mut output = Vec::new();
+        repl(
+            &mut counter,
+            "until count = 20\nrun\nstep 2\n".as_bytes(),
+            &mut output,
+        );
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("before condition was met, after 4 step(s)"));
+        assert!(output.contains("Took 0 step(s), 4 in total"));
+        assert!(output.contains("Took 0 step(s), 4 in total\n> Took 0 step(s), 4 in total"));
+    }
+}
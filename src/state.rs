@@ -0,0 +1,100 @@
+// Saving and restoring puzzle state as JSON, so long-running computations can be paused, inspected and resumed
+// rather than always rerun from scratch.
+
+use serde::{de::DeserializeOwned, Serialize};
+
+#[derive(Debug)]
+pub enum StateError {
+    Unsupported,
+    Io(std::io::Error),
+    Json(serde_json::Error),
+}
+
+impl std::fmt::Display for StateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            StateError::Unsupported => write!(f, "this state can't be saved"),
+            StateError::Io(error) => write!(f, "{}", error),
+            StateError::Json(error) => write!(f, "invalid state: {}", error),
+        }
+    }
+}
+
+impl From<std::io::Error> for StateError {
+    fn from(error: std::io::Error) -> Self {
+        StateError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for StateError {
+    fn from(error: serde_json::Error) -> Self {
+        StateError::Json(error)
+    }
+}
+
+pub fn to_json<T: Serialize>(state: &T) -> Result<String, StateError> {
+    Ok(serde_json::to_string(state)?)
+}
+
+pub fn from_json<T: DeserializeOwned>(json: &str) -> Result<T, StateError> {
+    Ok(serde_json::from_str(json)?)
+}
+
+// JSON objects can only have string keys, so maps keyed by coordinates are stored as a list of (key, value) pairs.
+// Use with `#[serde(with = "crate::state::map_as_pairs")]`.
+pub mod map_as_pairs {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::HashMap;
+    use std::hash::Hash;
+
+    pub fn serialize<K, V, S>(map: &HashMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        K: Serialize,
+        V: Serialize,
+        S: Serializer,
+    {
+        serializer.collect_seq(map.iter())
+    }
+
+    pub fn deserialize<'de, K, V, D>(deserializer: D) -> Result<HashMap<K, V>, D::Error>
+    where
+        K: Deserialize<'de> + Eq + Hash,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Ok(Vec::<(K, V)>::deserialize(deserializer)?
+            .into_iter()
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::collections::HashMap;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Grid {
+        #[serde(with = "map_as_pairs")]
+        cells: HashMap<(i32, i32), char>,
+    }
+
+    #[test]
+    fn check_map_as_pairs() {
+        let grid = Grid {
+            cells: HashMap::from([((0, 0), '#'), ((-1, 2), '.')]),
+        };
+        let json = to_json(&grid).unwrap();
+        assert!(json.contains("[[-1,2],\".\"]"));
+        assert_eq!(from_json::<Grid>(&json).unwrap(), grid);
+    }
+
+    #[test]
+    fn check_bad_state() {
+        assert!(matches!(
+            from_json::<Grid>("{\"cells\": {}}"),
+            Err(StateError::Json(_))
+        ));
+    }
+}
@@ -6,7 +6,7 @@
 // For Part 2, we're asked to find the square of the difference between the two numbers in each line,
 // then return the sum of those.
 
-// When run with `cargo run 0`, the calling code in main.rs will load the input in the file inputs/0
+// When run with `cargo run 0`, the calling code in main.rs will load the input in the file inputs/<year>/0
 // and pass that through as the input to the day00() function here as a single &str.
 // This function (as with all dayXX templates in this repo) returns two Strings, which will be printed
 // out to terminal following the labels "Part 1:" and "Part 2: respectively".
@@ -0,0 +1,424 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::io::BufRead;
+
+pub fn day01(input_lines: &str) -> (String, String) {
+    let top_three = top_elves(input_lines.as_bytes(), 3)
+        .unwrap_or_else(|error| panic!("Invalid inventory: {}", error));
+    let answer1 = top_three.first().map_or(0, |elf| elf.calories);
+    let answer2 = top_three.iter().map(|elf| elf.calories).sum::<u64>();
+    (format!("{}", answer1), format!("{}", answer2))
+}
+
+// `cargo run -- report 1 [buckets]` describes how the calories are spread between the elves.
+pub fn day01_report(input_lines: &str, args: &[String]) -> String {
+    let buckets = args.first().map_or(10, |buckets| {
+        buckets
+            .parse::<usize>()
+            .expect("Please provide the number of histogram buckets as an integer.")
+    });
+    let elves = parse_inventories(input_lines.as_bytes())
+        .unwrap_or_else(|error| panic!("Invalid inventory: {}", error));
+    InventoryStats::from_inventories(&elves).report(buckets)
+}
+
+#[derive(Debug)]
+pub enum InventoryError {
+    Io(std::io::Error),
+    InvalidItem { line: usize, item: String },
+}
+
+impl std::fmt::Display for InventoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            InventoryError::Io(error) => write!(f, "{}", error),
+            InventoryError::InvalidItem { line, item } => {
+                write!(
+                    f,
+                    "expected a number of calories on line {}, not {:?}",
+                    line, item
+                )
+            }
+        }
+    }
+}
+
+impl From<std::io::Error> for InventoryError {
+    fn from(error: std::io::Error) -> Self {
+        InventoryError::Io(error)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Elf {
+    // Where the elf comes in the inventory, counting from 0.
+    pub position: usize,
+    pub calories: u64,
+}
+
+// Stream the inventory from the reader, keeping only the k elves carrying the most calories so inventories far too
+// big to hold in memory can still be searched. Returns those elves most calories first, with ties going to the elf
+// that came first.
+pub fn top_elves(reader: impl BufRead, k: usize) -> Result<Vec<Elf>, InventoryError> {
+    // A min-heap of the best so far, so the weakest contender is always the one to drop.
+    let mut top: BinaryHeap<Reverse<(u64, Reverse<usize>)>> = BinaryHeap::with_capacity(k + 1);
+    let mut position = 0;
+    read_elves(reader, |items| {
+        top.push(Reverse((items.iter().sum(), Reverse(position))));
+        if top.len() > k {
+            top.pop();
+        }
+        position += 1;
+    })?;
+
+    Ok(top
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse((calories, Reverse(position)))| Elf { position, calories })
+        .collect())
+}
+
+// Every elf's items, in inventory order.
+pub fn parse_inventories(reader: impl BufRead) -> Result<Vec<Vec<u64>>, InventoryError> {
+    let mut elves = vec![];
+    read_elves(reader, |items| elves.push(items))?;
+    Ok(elves)
+}
+
+// Hand each elf's items to on_elf in turn, so only one elf needs to be in memory at a time.
+fn read_elves(
+    reader: impl BufRead,
+    mut on_elf: impl FnMut(Vec<u64>),
+) -> Result<(), InventoryError> {
+    let mut items = vec![];
+    for (idx, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() {
+            if !items.is_empty() {
+                on_elf(std::mem::take(&mut items));
+            }
+        } else {
+            items.push(parse_elf(idx + 1, line)?);
+        }
+    }
+    if !items.is_empty() {
+        on_elf(items);
+    }
+    Ok(())
+}
+
+pub fn parse_elf(line: usize, item: &str) -> Result<u64, InventoryError> {
+    item.parse::<u64>()
+        .map_err(|_| InventoryError::InvalidItem {
+            line,
+            item: item.to_string(),
+        })
+}
+
+pub struct InventoryStats {
+    // Each elf's total calories and number of items, in inventory order.
+    totals: Vec<u64>,
+    item_counts: Vec<usize>,
+    sorted_totals: Vec<u64>,
+}
+
+impl InventoryStats {
+    pub fn from_inventories(elves: &[Vec<u64>]) -> Self {
+        let totals = elves
+            .iter()
+            .map(|items| items.iter().sum())
+            .collect::<Vec<u64>>();
+        let mut sorted_totals = totals.clone();
+        sorted_totals.sort_unstable();
+        Self {
+            totals,
+            item_counts: elves.iter().map(|items| items.len()).collect(),
+            sorted_totals,
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.totals.len()
+    }
+
+    pub fn totals(&self) -> &[u64] {
+        &self.totals
+    }
+
+    pub fn item_counts(&self) -> &[usize] {
+        &self.item_counts
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        if self.totals.is_empty() {
+            return None;
+        }
+        Some(self.totals.iter().sum::<u64>() as f64 / self.count() as f64)
+    }
+
+    // With an even number of elves this is halfway between the middle two.
+    pub fn median(&self) -> Option<f64> {
+        let count = self.count();
+        if count == 0 {
+            return None;
+        }
+        let upper = self.sorted_totals[count / 2] as f64;
+        if count % 2 == 1 {
+            Some(upper)
+        } else {
+            Some((self.sorted_totals[count / 2 - 1] as f64 + upper) / 2.0)
+        }
+    }
+
+    // The nearest-rank percentile: the smallest total that at least `percent`% of elves carry no more than.
+    pub fn percentile(&self, percent: f64) -> Option<u64> {
+        if self.totals.is_empty() || !(0.0..=100.0).contains(&percent) {
+            return None;
+        }
+        let rank = (percent / 100.0 * self.count() as f64).ceil() as usize;
+        Some(self.sorted_totals[rank.max(1) - 1])
+    }
+
+    // A bar chart of how many elves' totals fall in each of `buckets` equal ranges, widest bar `width` characters.
+    pub fn histogram(&self, buckets: usize, width: usize) -> String {
+        let (Some(&min), Some(&max)) = (self.sorted_totals.first(), self.sorted_totals.last())
+        else {
+            return String::new();
+        };
+        let buckets = buckets.max(1);
+        let bucket_size = (max - min) / buckets as u64 + 1;
+        let mut counts = vec![0; buckets];
+        for total in &self.sorted_totals {
+            counts[((total - min) / bucket_size) as usize] += 1;
+        }
+        let biggest = *counts.iter().max().unwrap();
+        let label_width = (min + bucket_size * buckets as u64).to_string().len();
+        counts
+            .iter()
+            .enumerate()
+            .map(|(bucket, &count)| {
+                let start = min + bucket as u64 * bucket_size;
+                format!(
+                    "{:>label_width$} - {:>label_width$} | {} {}\n",
+                    start,
+                    start + bucket_size - 1,
+                    "#".repeat(count * width / biggest),
+                    count,
+                )
+            })
+            .collect()
+    }
+
+    pub fn report(&self, buckets: usize) -> String {
+        let Some(mean) = self.mean() else {
+            return "No elves in the inventory\n".to_string();
+        };
+        let percentiles = [10.0, 25.0, 50.0, 75.0, 90.0, 99.0]
+            .iter()
+            .map(|&percent| format!("p{} {}", percent, self.percentile(percent).unwrap()))
+            .collect::<Vec<String>>()
+            .join(", ");
+        let item_counts = self.item_counts();
+        let items = item_counts.iter().sum::<usize>();
+        format!(
+            "Elves: {}\nTotal calories: {}\nMean: {:.1}\nMedian: {}\nPercentiles: {}\nItems per elf: {} to {}, {:.1} on average\n\n{}",
+            self.count(),
+            self.totals().iter().sum::<u64>(),
+            mean,
+            self.median().unwrap(),
+            percentiles,
+            item_counts.iter().min().unwrap(),
+            item_counts.iter().max().unwrap(),
+            items as f64 / self.count() as f64,
+            self.histogram(buckets, 40)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_day01_part1_case1() {
+        assert_eq!(
+            day01(
+                "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000"
+            )
+            .0,
+            "24000".to_string()
+        )
+    }
+
+    #[test]
+    fn check_day01_part2_case1() {
+        assert_eq!(
+            day01(
+                "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000"
+            )
+            .1,
+            "45000".to_string()
+        )
+    }
+
+    #[test]
+    fn check_day01_both_case1() {
+        assert_eq!(
+            day01(
+                "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000"
+            ),
+            ("24000".to_string(), "45000".to_string())
+        )
+    }
+
+    #[test]
+    fn check_day01_top_elves() {
+        let inventory = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+        assert_eq!(
+            top_elves(inventory.as_bytes(), 2).unwrap(),
+            vec![
+                Elf {
+                    position: 3,
+                    calories: 24000
+                },
+                Elf {
+                    position: 2,
+                    calories: 11000
+                },
+            ]
+        );
+        assert_eq!(top_elves(inventory.as_bytes(), 0).unwrap(), vec![]);
+        assert_eq!(top_elves(inventory.as_bytes(), 10).unwrap().len(), 5);
+    }
+
+    #[test]
+    fn check_day01_top_elves_ties_and_large_totals() {
+        let inventory = "3000000000\n3000000000\n\n\n\n5\n\n6000000000\n";
+        assert_eq!(
+            top_elves(std::io::Cursor::new(inventory), 1).unwrap(),
+            vec![Elf {
+                position: 0,
+                calories: 6000000000
+            }]
+        );
+    }
+
+    #[test]
+    fn check_day01_few_elves() {
+        assert_eq!(day01("100\n200"), ("300".to_string(), "300".to_string()));
+        assert_eq!(day01(""), ("0".to_string(), "0".to_string()));
+    }
+
+    #[test]
+    fn check_day01_inventory_stats() {
+        let elves = parse_inventories(
+            "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000"
+                .as_bytes(),
+        )
+        .unwrap();
+        let stats = InventoryStats::from_inventories(&elves);
+        assert_eq!(stats.count(), 5);
+        assert_eq!(stats.totals(), [6000, 4000, 11000, 24000, 10000]);
+        assert_eq!(stats.item_counts(), [3, 1, 2, 3, 1]);
+        assert_eq!(stats.mean(), Some(11000.0));
+        assert_eq!(stats.median(), Some(10000.0));
+        assert_eq!(stats.percentile(0.0), Some(4000));
+        assert_eq!(stats.percentile(40.0), Some(6000));
+        assert_eq!(stats.percentile(41.0), Some(10000));
+        assert_eq!(stats.percentile(100.0), Some(24000));
+        assert_eq!(
+            stats.histogram(2, 10),
+            " 4000 - 14000 | ########## 4
+14001 - 24001 | ## 1
+"
+        );
+
+        let stats = InventoryStats::from_inventories(&elves[..2]);
+        assert_eq!(stats.median(), Some(5000.0));
+        let stats = InventoryStats::from_inventories(&[]);
+        assert_eq!(
+            (stats.mean(), stats.median(), stats.percentile(50.0)),
+            (None, None, None)
+        );
+        assert_eq!(stats.histogram(5, 10), "");
+    }
+
+    #[test]
+    fn check_day01_invalid_item() {
+        let error = parse_inventories("1000\n\n2000\n20OO\n".as_bytes()).unwrap_err();
+        assert!(matches!(
+            error,
+            InventoryError::InvalidItem { line: 4, ref item } if item == "20OO"
+        ));
+        assert!(matches!(
+            top_elves("1000\n-5".as_bytes(), 3),
+            Err(InventoryError::InvalidItem { line: 2, .. })
+        ));
+    }
+}
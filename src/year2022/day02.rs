@@ -0,0 +1,647 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
+// The rules from the puzzle, in the format GameRules::from_definition reads.
+const ROCK_PAPER_SCISSORS: &str = "move rock A X 1
+move paper B Y 2
+move scissors C Z 3
+rock beats scissors
+paper beats rock
+scissors beats paper
+outcomes 0 3 6";
+
+// The most ways of reading the guide's symbols as moves that search_interpretations will try.
+const MAX_READINGS: usize = 100_000;
+
+pub fn day02(input_lines: &str) -> (String, String) {
+    let rules = GameRules::from_definition(ROCK_PAPER_SCISSORS).unwrap();
+    let answer1 = rules
+        .score_guide(input_lines, &rules.shape_reading())
+        .unwrap_or_else(|error| panic!("Invalid strategy guide: {}", error));
+    let answer2 = rules
+        .score_guide(input_lines, &Interpretation::lose_draw_win())
+        .unwrap_or_else(|error| panic!("Invalid strategy guide: {}", error));
+    (format!("{}", answer1), format!("{}", answer2))
+}
+
+// `cargo run -- report 2 [target score] [move,move,...]` scores the guide under every reading of its second column,
+// best first, flagging any that give the target score. Listing moves plays a cyclic game of them (see
+// GameRules::cyclic) rather than plain rock-paper-scissors.
+pub fn day02_report(input_lines: &str, args: &[String]) -> String {
+    let target = args.first().map(|target| {
+        target
+            .parse::<u64>()
+            .expect("Please provide the target score as an integer.")
+    });
+    let rules = match args.get(1) {
+        Some(moves) => GameRules::cyclic(&moves.split(',').collect::<Vec<&str>>(), [0, 3, 6]),
+        None => GameRules::from_definition(ROCK_PAPER_SCISSORS),
+    }
+    .unwrap_or_else(|error| panic!("{}", error));
+    let readings = rules
+        .search_interpretations(input_lines)
+        .unwrap_or_else(|error| panic!("Invalid strategy guide: {}", error));
+    let mut report = String::new();
+    for (idx, (interpretation, score)) in readings.iter().enumerate() {
+        let mut notes = vec![];
+        if idx == 0 {
+            notes.push("best");
+        }
+        if idx == readings.len() - 1 {
+            notes.push("worst");
+        }
+        if target == Some(*score) {
+            notes.push("matches target");
+        }
+        report += &format!("{:>8}  {}", score, interpretation.describe(&rules));
+        if !notes.is_empty() {
+            report += &format!("  ({})", notes.join(", "));
+        }
+        report.push('\n');
+    }
+    if let Some(target) = target {
+        if !readings.iter().any(|(_, score)| *score == target) {
+            report += &format!("No reading scores {}\n", target);
+        }
+    }
+    report
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+#[derive(Debug)]
+pub enum GameError {
+    InvalidRules(String),
+    InvalidLine(usize),
+    UnknownSymbol {
+        line: usize,
+        symbol: String,
+    },
+    ImpossibleOutcome {
+        line: usize,
+        outcome: Outcome,
+    },
+    TooManySymbols {
+        symbols: usize,
+        available: usize,
+        meaning: &'static str,
+    },
+    TooManyReadings {
+        symbols: usize,
+        moves: usize,
+    },
+}
+
+impl std::fmt::Display for GameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            GameError::InvalidRules(reason) => write!(f, "invalid rules: {}", reason),
+            GameError::InvalidLine(line) => write!(f, "expected two moves on line {}", line),
+            GameError::UnknownSymbol { line, symbol } => {
+                write!(f, "unknown move {:?} on line {}", symbol, line)
+            }
+            GameError::ImpossibleOutcome { line, outcome } => {
+                write!(f, "no move can {:?} on line {}", outcome, line)
+            }
+            GameError::TooManySymbols {
+                symbols,
+                available,
+                meaning,
+            } => write!(
+                f,
+                "the guide has {} symbols but there are only {} {}s to read them as",
+                symbols, available, meaning
+            ),
+            GameError::TooManyReadings { symbols, moves } => write!(
+                f,
+                "there are over {} ways to read {} symbols as {} moves, too many to try",
+                MAX_READINGS, symbols, moves
+            ),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Move {
+    name: String,
+    opponent_symbol: String,
+    player_symbol: String,
+    score: u32,
+}
+
+// What the second column of the strategy guide means.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Interpretation {
+    // The move to play, by its index in the rules.
+    Shapes(HashMap<String, usize>),
+    // The outcome to aim for. Where several moves get it, the best-scoring one is played.
+    Outcomes(HashMap<String, Outcome>),
+}
+
+impl Interpretation {
+    // Part 2's reading: X loses, Y draws and Z wins.
+    pub fn lose_draw_win() -> Self {
+        Interpretation::Outcomes(HashMap::from([
+            ("X".to_string(), Outcome::Lose),
+            ("Y".to_string(), Outcome::Draw),
+            ("Z".to_string(), Outcome::Win),
+        ]))
+    }
+
+    // e.g. "X=rock, Y=paper, Z=scissors" or "X=lose, Y=draw, Z=win".
+    pub fn describe(&self, rules: &GameRules) -> String {
+        let meanings = match self {
+            Interpretation::Shapes(shapes) => shapes
+                .iter()
+                .map(|(symbol, &player)| (symbol, rules.move_names()[player].to_string()))
+                .collect::<Vec<(&String, String)>>(),
+            Interpretation::Outcomes(outcomes) => outcomes
+                .iter()
+                .map(|(symbol, outcome)| (symbol, format!("{:?}", outcome).to_lowercase()))
+                .collect(),
+        };
+        meanings
+            .iter()
+            .sorted()
+            .map(|(symbol, meaning)| format!("{}={}", symbol, meaning))
+            .join(", ")
+    }
+}
+
+// A game where each player picks a move at the same time and the beat relation decides the winner. Any moves that
+// don't beat each other either way draw.
+#[derive(Debug)]
+pub struct GameRules {
+    moves: Vec<Move>,
+    // beats[a][b] is true if move a beats move b.
+    beats: Vec<Vec<bool>>,
+    // Indexed by Outcome.
+    outcome_scores: [u32; 3],
+}
+
+impl GameRules {
+    // Rules are written one per line:
+    //   move <name> <opponent's symbol> <player's symbol> <score>
+    //   <name> beats <name> [<name>...]
+    //   outcomes <lose score> <draw score> <win score>
+    // Blank lines and lines starting with # are ignored.
+    pub fn from_definition(definition: &str) -> Result<Self, GameError> {
+        let invalid = |reason: String| Err(GameError::InvalidRules(reason));
+        let mut moves: Vec<Move> = vec![];
+        let mut beat_pairs = vec![];
+        let mut outcome_scores = None;
+        for line in definition.lines().map(str::trim) {
+            let words = line.split_whitespace().collect::<Vec<&str>>();
+            match words[..] {
+                [] => {}
+                [comment, ..] if comment.starts_with('#') => {}
+                ["move", name, opponent_symbol, player_symbol, score] => {
+                    let Ok(score) = score.parse::<u32>() else {
+                        return invalid(format!("{} isn't a score", score));
+                    };
+                    moves.push(Move {
+                        name: name.to_string(),
+                        opponent_symbol: opponent_symbol.to_string(),
+                        player_symbol: player_symbol.to_string(),
+                        score,
+                    });
+                }
+                ["outcomes", lose, draw, win] => {
+                    let scores = [lose, draw, win].map(|score| score.parse::<u32>());
+                    let [Ok(lose), Ok(draw), Ok(win)] = scores else {
+                        return invalid(format!("bad outcome scores in {:?}", line));
+                    };
+                    outcome_scores = Some([lose, draw, win]);
+                }
+                [winner, "beats", ref losers @ ..] if !losers.is_empty() => {
+                    beat_pairs.extend(losers.iter().map(|loser| (winner, *loser)));
+                }
+                _ => return invalid(format!("can't understand {:?}", line)),
+            }
+        }
+
+        let Some(outcome_scores) = outcome_scores else {
+            return invalid("no outcome scores".to_string());
+        };
+        for (idx, game_move) in moves.iter().enumerate() {
+            if moves[..idx].iter().any(|other| {
+                other.name == game_move.name
+                    || other.opponent_symbol == game_move.opponent_symbol
+                    || other.player_symbol == game_move.player_symbol
+            }) {
+                return invalid(format!("{} clashes with an earlier move", game_move.name));
+            }
+        }
+        let find = |name: &str| moves.iter().position(|game_move| game_move.name == name);
+        let mut beats = vec![vec![false; moves.len()]; moves.len()];
+        for (winner, loser) in beat_pairs {
+            let (Some(winner), Some(loser)) = (find(winner), find(loser)) else {
+                return invalid(format!(
+                    "{} beats {} mentions an unknown move",
+                    winner, loser
+                ));
+            };
+            if winner == loser || beats[loser][winner] {
+                return invalid(format!(
+                    "{} and {} can't beat each other",
+                    moves[winner].name, moves[loser].name
+                ));
+            }
+            beats[winner][loser] = true;
+        }
+        Ok(Self {
+            moves,
+            beats,
+            outcome_scores,
+        })
+    }
+
+    // A balanced game over an odd number of moves, where each move beats the half of the others that come before it
+    // (wrapping round), like rock-paper-scissors-lizard-Spock when listed as rock, Spock, paper, lizard, scissors.
+    // Moves score 1, 2, 3... in order, and are written A, B, C... by the opponent and ...X, Y, Z by the player.
+    pub fn cyclic(names: &[&str], outcome_scores: [u32; 3]) -> Result<Self, GameError> {
+        let count = names.len();
+        if count.is_multiple_of(2) || count > 13 {
+            return Err(GameError::InvalidRules(format!(
+                "a cyclic game needs an odd number of moves, up to 13, not {}",
+                count
+            )));
+        }
+        let moves = names
+            .iter()
+            .enumerate()
+            .map(|(idx, name)| Move {
+                name: name.to_string(),
+                opponent_symbol: ((b'A' + idx as u8) as char).to_string(),
+                player_symbol: ((b'Z' + 1 + idx as u8 - count as u8) as char).to_string(),
+                score: idx as u32 + 1,
+            })
+            .collect();
+        let beats = (0..count)
+            .map(|winner| {
+                (0..count)
+                    .map(|loser| (1..=count / 2).contains(&((winner + count - loser) % count)))
+                    .collect()
+            })
+            .collect();
+        Ok(Self {
+            moves,
+            beats,
+            outcome_scores,
+        })
+    }
+
+    pub fn move_names(&self) -> Vec<&str> {
+        self.moves
+            .iter()
+            .map(|game_move| game_move.name.as_str())
+            .collect()
+    }
+
+    // Part 1's reading, where the second column is the player's symbol for a move.
+    pub fn shape_reading(&self) -> Interpretation {
+        Interpretation::Shapes(
+            self.moves
+                .iter()
+                .enumerate()
+                .map(|(idx, game_move)| (game_move.player_symbol.clone(), idx))
+                .collect(),
+        )
+    }
+
+    pub fn play(&self, opponent: usize, player: usize) -> Outcome {
+        if self.beats[player][opponent] {
+            Outcome::Win
+        } else if self.beats[opponent][player] {
+            Outcome::Lose
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    pub fn score_round(&self, opponent: usize, player: usize) -> u32 {
+        self.moves[player].score + self.outcome_scores[self.play(opponent, player) as usize]
+    }
+
+    // The best-scoring move that gets the outcome against the opponent's move, if any does.
+    pub fn choose_move(&self, opponent: usize, outcome: Outcome) -> Option<usize> {
+        (0..self.moves.len())
+            .filter(|&player| self.play(opponent, player) == outcome)
+            .max_by_key(|&player| self.moves[player].score)
+    }
+
+    // The total score from following the strategy guide, reading its second column as the interpretation says.
+    pub fn score_guide(
+        &self,
+        guide: &str,
+        interpretation: &Interpretation,
+    ) -> Result<u64, GameError> {
+        let mut score = 0;
+        for (idx, line) in guide.lines().enumerate() {
+            let line_number = idx + 1;
+            let unknown = |symbol: &str| GameError::UnknownSymbol {
+                line: line_number,
+                symbol: symbol.to_string(),
+            };
+            let symbols = line.split_whitespace().collect::<Vec<&str>>();
+            let (opponent_symbol, player_symbol) = match symbols[..] {
+                [] => continue,
+                [opponent_symbol, player_symbol] => (opponent_symbol, player_symbol),
+                _ => return Err(GameError::InvalidLine(line_number)),
+            };
+            let opponent = self
+                .moves
+                .iter()
+                .position(|game_move| game_move.opponent_symbol == opponent_symbol)
+                .ok_or_else(|| unknown(opponent_symbol))?;
+            let player = match interpretation {
+                Interpretation::Shapes(shapes) => *shapes
+                    .get(player_symbol)
+                    .ok_or_else(|| unknown(player_symbol))?,
+                Interpretation::Outcomes(outcomes) => {
+                    let outcome = *outcomes
+                        .get(player_symbol)
+                        .ok_or_else(|| unknown(player_symbol))?;
+                    self.choose_move(opponent, outcome)
+                        .ok_or(GameError::ImpossibleOutcome {
+                            line: line_number,
+                            outcome,
+                        })?
+                }
+            };
+            score += self.score_round(opponent, player) as u64;
+        }
+        Ok(score)
+    }
+
+    // Score the guide under every way of reading its second column as a different move per symbol, or a different
+    // outcome per symbol. Readings where some outcome can't be achieved are left out. Returns the readings best first.
+    // Guides with more symbols than there are outcomes only get the move readings. Every reading is tried, so guides
+    // with more symbols than moves, or with more than MAX_READINGS ways to assign the moves, are refused rather than
+    // searched partly or for ever.
+    pub fn search_interpretations(
+        &self,
+        guide: &str,
+    ) -> Result<Vec<(Interpretation, u64)>, GameError> {
+        let symbols = guide
+            .lines()
+            .filter_map(|line| line.split_whitespace().nth(1))
+            .unique()
+            .sorted()
+            .map(|symbol| symbol.to_string())
+            .collect::<Vec<String>>();
+        if symbols.len() > self.moves.len() {
+            return Err(GameError::TooManySymbols {
+                symbols: symbols.len(),
+                available: self.moves.len(),
+                meaning: "move",
+            });
+        }
+        let shape_reading_count = (self.moves.len() - symbols.len() + 1..=self.moves.len())
+            .try_fold(1_usize, |count, choices| count.checked_mul(choices));
+        if shape_reading_count.is_none_or(|count| count > MAX_READINGS) {
+            return Err(GameError::TooManyReadings {
+                symbols: symbols.len(),
+                moves: self.moves.len(),
+            });
+        }
+        let shape_readings = (0..self.moves.len())
+            .permutations(symbols.len())
+            .map(|moves| Interpretation::Shapes(symbols.iter().cloned().zip(moves).collect()));
+        // There are no ways to pick more outcomes than the three there are, so this is empty for larger guides.
+        let outcome_readings = [Outcome::Lose, Outcome::Draw, Outcome::Win]
+            .into_iter()
+            .permutations(symbols.len())
+            .map(|outcomes| {
+                Interpretation::Outcomes(symbols.iter().cloned().zip(outcomes).collect())
+            });
+
+        let mut readings = vec![];
+        for interpretation in shape_readings.chain(outcome_readings) {
+            match self.score_guide(guide, &interpretation) {
+                Ok(score) => readings.push((interpretation, score)),
+                Err(GameError::ImpossibleOutcome { .. }) => {}
+                Err(error) => return Err(error),
+            }
+        }
+        readings.sort_by_cached_key(|(interpretation, score)| {
+            (std::cmp::Reverse(*score), interpretation.describe(self))
+        });
+        Ok(readings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_day02_part1_case1() {
+        assert_eq!(day02("A Y").0, "8".to_string())
+    }
+
+    #[test]
+    fn check_day02_part2_case1() {
+        assert_eq!(day02("B X").1, "1".to_string())
+    }
+
+    #[test]
+    fn check_day02_both_case1() {
+        assert_eq!(day02("C Z"), ("6".to_string(), "7".to_string()))
+    }
+
+    #[test]
+    fn check_day02_both_case2() {
+        assert_eq!(
+            day02("A Y\nB X\nC Z\n"),
+            ("15".to_string(), "12".to_string())
+        )
+    }
+
+    #[test]
+    fn check_day02_cyclic_rules_match_definition() {
+        let defined = GameRules::from_definition(ROCK_PAPER_SCISSORS).unwrap();
+        let cyclic = GameRules::cyclic(&["rock", "paper", "scissors"], [0, 3, 6]).unwrap();
+        assert_eq!(cyclic.beats, defined.beats);
+        assert_eq!(cyclic.shape_reading(), defined.shape_reading());
+    }
+
+    #[test]
+    fn check_day02_rock_paper_scissors_lizard_spock() {
+        let rules = GameRules::cyclic(&["rock", "spock", "paper", "lizard", "scissors"], [0, 3, 6])
+            .unwrap();
+        let find = |name: &str| rules.move_names().iter().position(|n| *n == name).unwrap();
+        let (rock, spock, paper, lizard, scissors) = (
+            find("rock"),
+            find("spock"),
+            find("paper"),
+            find("lizard"),
+            find("scissors"),
+        );
+        assert_eq!(rules.play(scissors, rock), Outcome::Win);
+        assert_eq!(rules.play(lizard, rock), Outcome::Win);
+        assert_eq!(rules.play(spock, lizard), Outcome::Win);
+        assert_eq!(rules.play(paper, spock), Outcome::Lose);
+        assert_eq!(rules.play(spock, spock), Outcome::Draw);
+
+        // A is rock and V-Z are rock to scissors for the player. Lizard (4) beats Spock (B): 4 + 6.
+        let guide = "A V\nB Y\nE V\n";
+        assert_eq!(
+            rules.score_guide(guide, &rules.shape_reading()).unwrap(),
+            4 + 10 + 7
+        );
+        // Losing to rock is best done with scissors (5), winning against Spock with lizard (4).
+        assert_eq!(
+            rules
+                .score_guide("A X\nB Z\n", &Interpretation::lose_draw_win())
+                .unwrap(),
+            5 + 10
+        );
+    }
+
+    #[test]
+    fn check_day02_seven_moves() {
+        let rules = GameRules::cyclic(
+            &[
+                "rock", "fire", "scissors", "sponge", "paper", "air", "water",
+            ],
+            [0, 3, 6],
+        )
+        .unwrap();
+        // Each move beats the three before it, so rock loses to the three after it and beats the rest.
+        assert_eq!(
+            (1..7)
+                .map(|player| rules.play(0, player))
+                .collect::<Vec<Outcome>>(),
+            [
+                Outcome::Win,
+                Outcome::Win,
+                Outcome::Win,
+                Outcome::Lose,
+                Outcome::Lose,
+                Outcome::Lose
+            ]
+        );
+        assert_eq!(rules.score_guide("G T", &rules.shape_reading()).unwrap(), 7);
+    }
+
+    #[test]
+    fn check_day02_invalid_rules_and_guides() {
+        assert!(matches!(
+            GameRules::from_definition("move rock A X 1\nrock beats rock\noutcomes 0 3 6"),
+            Err(GameError::InvalidRules(_))
+        ));
+        assert!(matches!(
+            GameRules::from_definition("move rock A X 1\nmove paper B X 2\noutcomes 0 3 6"),
+            Err(GameError::InvalidRules(_))
+        ));
+        assert!(matches!(
+            GameRules::cyclic(&["rock", "paper"], [0, 3, 6]),
+            Err(GameError::InvalidRules(_))
+        ));
+
+        let rules = GameRules::from_definition(ROCK_PAPER_SCISSORS).unwrap();
+        assert!(matches!(
+            rules.score_guide("A Y\nD X", &rules.shape_reading()),
+            Err(GameError::UnknownSymbol { line: 2, .. })
+        ));
+        assert!(matches!(
+            rules.score_guide("A Y X", &rules.shape_reading()),
+            Err(GameError::InvalidLine(1))
+        ));
+        let unbeatable = GameRules::from_definition("move rock A X 1\noutcomes 0 3 6").unwrap();
+        assert!(matches!(
+            unbeatable.score_guide("A Z", &Interpretation::lose_draw_win()),
+            Err(GameError::ImpossibleOutcome {
+                line: 1,
+                outcome: Outcome::Win
+            })
+        ));
+    }
+
+    #[test]
+    fn check_day02_search_interpretations() {
+        let rules = GameRules::from_definition(ROCK_PAPER_SCISSORS).unwrap();
+        let guide = "A Y\nB X\nC Z\n";
+        let readings = rules.search_interpretations(guide).unwrap();
+        // 6 ways to assign the shapes and 6 to assign the outcomes.
+        assert_eq!(readings.len(), 12);
+        assert_eq!(
+            readings
+                .iter()
+                .map(|(interpretation, score)| (interpretation.describe(&rules), *score))
+                .filter(
+                    |(description, _)| description == "X=rock, Y=paper, Z=scissors"
+                        || description == "X=lose, Y=draw, Z=win"
+                )
+                .collect::<Vec<(String, u64)>>(),
+            [
+                ("X=rock, Y=paper, Z=scissors".to_string(), 15),
+                ("X=lose, Y=draw, Z=win".to_string(), 12)
+            ]
+        );
+        assert!(readings.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+        // Playing to win every round with the best shape: paper, scissors and rock for 3 wins.
+        assert_eq!(readings[0].1, 8 + 9 + 7);
+        assert_eq!(readings.last().unwrap().1, 1 + 2 + 3);
+
+        let report = day02_report(guide, &["15".to_string()]);
+        assert!(report.contains("      15  X=rock, Y=paper, Z=scissors  (matches target)\n"));
+        assert!(report.starts_with("      24  "));
+        assert!(report.contains("(worst)"));
+        // Five symbols can't each be a different outcome, but they can each be a different one of five moves. There are
+        // too many ways to pick three of 61 moves.
+        let lizard_spock =
+            GameRules::cyclic(&["rock", "spock", "paper", "lizard", "scissors"], [0, 3, 6])
+                .unwrap();
+        let readings = lizard_spock
+            .search_interpretations("A V\nB W\nC X\nD Y\nE Z\n")
+            .unwrap();
+        assert_eq!(readings.len(), 5 * 4 * 3 * 2);
+        assert!(readings
+            .iter()
+            .all(|(interpretation, _)| matches!(interpretation, Interpretation::Shapes(_))));
+        let names = (0..13)
+            .map(|idx| format!("m{}", idx))
+            .collect::<Vec<String>>();
+        let thirteen = GameRules::cyclic(
+            &names.iter().map(String::as_str).collect::<Vec<&str>>(),
+            [0, 3, 6],
+        )
+        .unwrap();
+        assert_eq!(
+            thirteen
+                .search_interpretations("A X\nB Y\nC Z\n")
+                .unwrap()
+                .len(),
+            13 * 12 * 11 + 6
+        );
+        let many_moves = (0..61)
+            .map(|idx| format!("move m{} O{} P{} 1\n", idx, idx, idx))
+            .collect::<String>()
+            + "outcomes 0 3 6";
+        assert!(matches!(
+            GameRules::from_definition(&many_moves)
+                .unwrap()
+                .search_interpretations("O0 X\nO1 Y\nO2 Z\n"),
+            Err(GameError::TooManyReadings {
+                symbols: 3,
+                moves: 61
+            })
+        ));
+        assert_eq!(
+            rules
+                .search_interpretations("A V\nB W\nC X\nD Y\n")
+                .err()
+                .unwrap()
+                .to_string(),
+            "the guide has 4 symbols but there are only 3 moves to read them as"
+        );
+    }
+}
@@ -0,0 +1,305 @@
+pub fn day03(input_lines: &str) -> (String, String) {
+    let analysis = analyse_rucksacks(input_lines, 2, 3)
+        .unwrap_or_else(|error| panic!("Invalid rucksacks: {}", error));
+    let answer1 = analysis
+        .misplaced
+        .iter()
+        .map(|items| items.priority())
+        .sum::<u32>();
+    let answer2 = analysis
+        .badges
+        .iter()
+        .map(|items| items.priority())
+        .sum::<u32>();
+    (format!("{}", answer1), format!("{}", answer2))
+}
+
+// `cargo run -- report 3 [compartments] [group size]` lists the items in every compartment of each rucksack, and the
+// badges of each group.
+pub fn day03_report(input_lines: &str, args: &[String]) -> String {
+    let arg = |idx: usize, default: usize| {
+        args.get(idx).map_or(default, |arg| {
+            arg.parse::<usize>()
+                .expect("Please provide the compartments and group size as integers.")
+        })
+    };
+    let analysis = analyse_rucksacks(input_lines, arg(0, 2), arg(1, 3))
+        .unwrap_or_else(|error| panic!("Invalid rucksacks: {}", error));
+    let describe = |items: &ItemSet| match items.items() {
+        types if types.is_empty() => "none".to_string(),
+        types => format!(
+            "{} (priority {})",
+            types.iter().collect::<String>(),
+            items.priority()
+        ),
+    };
+    let mut report = String::new();
+    for (idx, items) in analysis.misplaced.iter().enumerate() {
+        report += &format!("Rucksack {}: {}\n", idx + 1, describe(items));
+    }
+    for (idx, items) in analysis.badges.iter().enumerate() {
+        report += &format!("Group {}: {}\n", idx + 1, describe(items));
+    }
+    report
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RucksackError {
+    InvalidItem { line: usize, item: char },
+    UnevenCompartments { line: usize, compartments: usize },
+    IncompleteGroup { line: usize, group_size: usize },
+}
+
+impl std::fmt::Display for RucksackError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            RucksackError::InvalidItem { line, item } => {
+                write!(f, "{:?} on line {} isn't an item type", item, line)
+            }
+            RucksackError::UnevenCompartments { line, compartments } => write!(
+                f,
+                "the rucksack on line {} can't be split into {} equal compartments",
+                line, compartments
+            ),
+            RucksackError::IncompleteGroup { line, group_size } => write!(
+                f,
+                "the group starting on line {} has fewer than {} elves",
+                line, group_size
+            ),
+        }
+    }
+}
+
+// A set of item types, as a bitmask where bit n is the item type with priority n.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub fn from_items(items: &str) -> Result<Self, char> {
+        items
+            .chars()
+            .try_fold(0, |mask, item| Ok(mask | 1 << priority(item).ok_or(item)?))
+            .map(ItemSet)
+    }
+
+    pub fn all() -> Self {
+        ItemSet(u64::MAX)
+    }
+
+    pub fn intersection(self, other: Self) -> Self {
+        ItemSet(self.0 & other.0)
+    }
+
+    // The item types in the set, in priority order.
+    pub fn items(self) -> Vec<char> {
+        (1..=52)
+            .filter(|priority| self.0 & 1 << priority != 0)
+            .map(item_type)
+            .collect()
+    }
+
+    // The sum of the priorities of the item types in the set.
+    pub fn priority(self) -> u32 {
+        (1..=52)
+            .filter(|priority| self.0 & 1 << priority != 0)
+            .sum()
+    }
+}
+
+// a-z have priorities 1-26, and A-Z 27-52.
+pub fn priority(item: char) -> Option<u32> {
+    match item {
+        'a'..='z' => Some(item as u32 - 'a' as u32 + 1),
+        'A'..='Z' => Some(item as u32 - 'A' as u32 + 27),
+        _ => None,
+    }
+}
+
+fn item_type(priority: u32) -> char {
+    match priority {
+        1..=26 => (b'a' + priority as u8 - 1) as char,
+        _ => (b'A' + priority as u8 - 27) as char,
+    }
+}
+
+pub struct Rucksack {
+    compartments: Vec<ItemSet>,
+    contents: ItemSet,
+}
+
+impl Rucksack {
+    // Split the rucksack's contents into equally sized compartments.
+    pub fn new(line: usize, contents: &str, compartments: usize) -> Result<Self, RucksackError> {
+        let items = |items: &str| {
+            ItemSet::from_items(items).map_err(|item| RucksackError::InvalidItem { line, item })
+        };
+        let contents_set = items(contents)?;
+        if compartments == 0 || !contents.len().is_multiple_of(compartments) {
+            return Err(RucksackError::UnevenCompartments { line, compartments });
+        }
+        // Every item type is ASCII, so the contents can be split by byte.
+        let compartment_size = contents.len() / compartments;
+        Ok(Self {
+            compartments: (0..compartments)
+                .map(|idx| items(&contents[idx * compartment_size..(idx + 1) * compartment_size]))
+                .collect::<Result<Vec<ItemSet>, RucksackError>>()?,
+            contents: contents_set,
+        })
+    }
+
+    // The item types that have been packed into every compartment.
+    pub fn misplaced_items(&self) -> ItemSet {
+        self.compartments
+            .iter()
+            .fold(ItemSet::all(), |common, compartment| {
+                common.intersection(*compartment)
+            })
+    }
+}
+
+// The item types carried by every elf in the group.
+pub fn group_badges(group: &[Rucksack]) -> ItemSet {
+    group.iter().fold(ItemSet::all(), |common, rucksack| {
+        common.intersection(rucksack.contents)
+    })
+}
+
+pub struct RucksackAnalysis {
+    // The items in every compartment of each rucksack, and the badges of each group, in input order.
+    pub misplaced: Vec<ItemSet>,
+    pub badges: Vec<ItemSet>,
+}
+
+pub fn analyse_rucksacks(
+    input_lines: &str,
+    compartments: usize,
+    group_size: usize,
+) -> Result<RucksackAnalysis, RucksackError> {
+    let rucksacks = input_lines
+        .lines()
+        .enumerate()
+        .map(|(idx, contents)| Rucksack::new(idx + 1, contents.trim(), compartments))
+        .collect::<Result<Vec<Rucksack>, RucksackError>>()?;
+    if group_size == 0 || !rucksacks.len().is_multiple_of(group_size) {
+        return Err(RucksackError::IncompleteGroup {
+            line: rucksacks.len() / group_size.max(1) * group_size + 1,
+            group_size,
+        });
+    }
+    Ok(RucksackAnalysis {
+        misplaced: rucksacks.iter().map(Rucksack::misplaced_items).collect(),
+        badges: rucksacks.chunks(group_size).map(group_badges).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw";
+
+    #[test]
+    fn check_day03_part1_case1() {
+        assert_eq!(
+            day03(
+                "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw"
+            )
+            .0,
+            "157".to_string()
+        )
+    }
+
+    #[test]
+    fn check_day03_part2_case1() {
+        assert_eq!(
+            day03(
+                "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw"
+            )
+            .1,
+            "70".to_string()
+        )
+    }
+
+    #[test]
+    fn check_day03_both_case1() {
+        assert_eq!(
+            day03(
+                "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw"
+            ),
+            ("157".to_string(), "70".to_string())
+        )
+    }
+
+    #[test]
+    fn check_day03_offending_items() {
+        let analysis = analyse_rucksacks(EXAMPLE, 2, 3).unwrap();
+        assert_eq!(
+            analysis
+                .misplaced
+                .iter()
+                .map(|items| items.items())
+                .collect::<Vec<Vec<char>>>(),
+            [['p'], ['L'], ['P'], ['v'], ['t'], ['s']]
+        );
+        assert_eq!(analysis.badges[0].items(), ['r']);
+        assert_eq!(analysis.badges[1].items(), ['Z']);
+
+        // In pairs, with badges listed in priority order. No one item is carried by all six elves.
+        let analysis = analyse_rucksacks(EXAMPLE, 1, 2).unwrap();
+        assert_eq!(analysis.badges[0].items(), ['f', 'r', 's', 'F', 'M']);
+        assert_eq!(analysis.badges[2].items(), ['G', 'J', 'Z']);
+        assert_eq!(
+            analyse_rucksacks(EXAMPLE, 1, 6).unwrap().badges[0].items(),
+            []
+        );
+    }
+
+    #[test]
+    fn check_day03_compartments() {
+        let rucksack = Rucksack::new(1, "abcaxcabz", 3).unwrap();
+        assert_eq!(rucksack.misplaced_items().items(), ['a']);
+        assert_eq!(rucksack.misplaced_items().priority(), 1);
+        assert_eq!(
+            Rucksack::new(1, "abcaxcab", 3).err(),
+            Some(RucksackError::UnevenCompartments {
+                line: 1,
+                compartments: 3
+            })
+        );
+    }
+
+    #[test]
+    fn check_day03_errors() {
+        assert_eq!(
+            analyse_rucksacks("abab\nab1b", 2, 1).err(),
+            Some(RucksackError::InvalidItem { line: 2, item: '1' })
+        );
+        assert_eq!(
+            analyse_rucksacks("abab\nabab\nabab\nabab", 2, 3).err(),
+            Some(RucksackError::IncompleteGroup {
+                line: 4,
+                group_size: 3
+            })
+        );
+    }
+}
@@ -0,0 +1,431 @@
+use itertools::Itertools;
+
+pub fn day04(input_lines: &str) -> (String, String) {
+    let pairs = input_lines
+        .lines()
+        .map(parse_pair)
+        .collect::<Vec<(Interval, Interval)>>();
+    let answer1 = pairs
+        .iter()
+        .filter(|(elf1, elf2)| elf1.contains(*elf2) || elf2.contains(*elf1))
+        .count();
+    let answer2 = pairs
+        .iter()
+        .filter(|(elf1, elf2)| elf1.overlaps(*elf2))
+        .count();
+    (format!("{}", answer1), format!("{}", answer2))
+}
+
+// `cargo run -- report 4` describes how much of the camp the elves cover between them, where they get in each other's
+// way, and which of them are actually needed. Elves are numbered from 1 in the order they appear.
+pub fn day04_report(input_lines: &str, _args: &[String]) -> String {
+    let assignments = parse_assignments(input_lines);
+    let analysis = analyse_assignments(&assignments);
+    let list = |intervals: &[Interval]| {
+        intervals
+            .iter()
+            .map(|interval| interval.to_string())
+            .join(", ")
+    };
+    let mut report = format!(
+        "Elves: {}\nSections covered: {}\nSections covered by exactly one elf: {}\n",
+        assignments.len(),
+        total_covered(&assignments),
+        covered_exactly_once(&assignments)
+    );
+    report += &format!(
+        "Most elves on one section: {}, on sections {}\n",
+        analysis.max_depth,
+        list(&analysis.deepest_sections)
+    );
+    report += &format!("Redundant elves: {}\n", analysis.redundant.len());
+    for (elf, covered_by) in &analysis.redundant {
+        report += &format!(
+            "  Elf {} ({}) is covered by elf {} ({})\n",
+            elf + 1,
+            assignments[*elf],
+            covered_by + 1,
+            assignments[*covered_by]
+        );
+    }
+    report += &format!(
+        "Fewest elves covering every section: {}\n  {}\n",
+        analysis.minimal_cover.len(),
+        analysis
+            .minimal_cover
+            .iter()
+            .map(|elf| format!("{} ({})", elf + 1, assignments[*elf]))
+            .join(", ")
+    );
+    report
+}
+
+// Every elf's assignment in the file, two to a line.
+pub fn parse_assignments(input_lines: &str) -> Vec<Interval> {
+    input_lines
+        .lines()
+        .flat_map(|line| {
+            let (elf1, elf2) = parse_pair(line);
+            [elf1, elf2]
+        })
+        .collect()
+}
+
+// An inclusive range of sections, which is never empty.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval {
+    start: u64,
+    end: u64,
+}
+
+impl Interval {
+    pub fn new(start: u64, end: u64) -> Option<Self> {
+        (start <= end).then_some(Self { start, end })
+    }
+
+    // This is a u128 as 0-u64::MAX has one more section than a u64 can count.
+    pub fn section_count(self) -> u128 {
+        (self.end - self.start) as u128 + 1
+    }
+
+    pub fn contains(self, other: Self) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    pub fn overlaps(self, other: Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    pub fn intersection(self, other: Self) -> Option<Self> {
+        Interval::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    // The union as a single interval, if the two overlap or are next to each other.
+    pub fn union(self, other: Self) -> Option<Self> {
+        let (first, second) = (self.min(other), self.max(other));
+        if second.start > first.end.saturating_add(1) {
+            return None;
+        }
+        Some(Self {
+            start: first.start,
+            end: first.end.max(second.end),
+        })
+    }
+}
+
+impl std::fmt::Display for Interval {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+// The sections covered by any of the intervals, as the fewest intervals possible in section order.
+pub fn merge(intervals: &[Interval]) -> Vec<Interval> {
+    let mut sorted = intervals.to_vec();
+    sorted.sort_unstable();
+    let mut merged: Vec<Interval> = vec![];
+    for interval in sorted {
+        match merged.last_mut() {
+            Some(last) if last.union(interval).is_some() => *last = last.union(interval).unwrap(),
+            _ => merged.push(interval),
+        }
+    }
+    merged
+}
+
+// Split the covered sections into runs where the same number of intervals overlap, returning each run in section
+// order along with how many intervals cover it.
+pub fn coverage(intervals: &[Interval]) -> Vec<(Interval, usize)> {
+    // Sweep along the sections, counting intervals in at their start and out after their end. Ends are kept as the
+    // section after, which needs a u128 for an interval ending on u64::MAX.
+    let mut events = intervals
+        .iter()
+        .flat_map(|interval| [(interval.start as u128, 1), (interval.end as u128 + 1, -1)])
+        .collect::<Vec<(u128, i64)>>();
+    events.sort_unstable();
+    let mut runs = vec![];
+    let mut depth = 0;
+    for (idx, &(section, change)) in events.iter().enumerate() {
+        depth += change;
+        let Some(&(next_section, _)) = events.get(idx + 1) else {
+            break;
+        };
+        if depth > 0 && next_section > section {
+            runs.push((
+                Interval {
+                    start: section as u64,
+                    end: (next_section - 1) as u64,
+                },
+                depth as usize,
+            ));
+        }
+    }
+    runs
+}
+
+pub fn total_covered(intervals: &[Interval]) -> u128 {
+    merge(intervals)
+        .iter()
+        .map(|interval| interval.section_count())
+        .sum()
+}
+
+pub fn covered_exactly_once(intervals: &[Interval]) -> u128 {
+    coverage(intervals)
+        .iter()
+        .filter(|(_, depth)| *depth == 1)
+        .map(|(interval, _)| interval.section_count())
+        .sum()
+}
+
+// How a whole camp's worth of assignments (indexed by elf) fit together.
+#[derive(Debug, PartialEq)]
+pub struct AssignmentAnalysis {
+    // The most elves assigned to any one section, and every section with that many, as intervals in section order.
+    pub max_depth: usize,
+    pub deepest_sections: Vec<Interval>,
+    // Each elf whose assignment lies within another elf's, along with that other elf. When two elves have the same
+    // assignment, only the later one is counted as redundant.
+    pub redundant: Vec<(usize, usize)>,
+    // The fewest elves whose assignments still cover every section that's covered now, in section order.
+    pub minimal_cover: Vec<usize>,
+}
+
+pub fn analyse_assignments(assignments: &[Interval]) -> AssignmentAnalysis {
+    let runs = coverage(assignments);
+    let max_depth = runs.iter().map(|(_, depth)| *depth).max().unwrap_or(0);
+    let deepest_sections = merge(
+        &runs
+            .iter()
+            .filter(|(_, depth)| *depth == max_depth)
+            .map(|(interval, _)| *interval)
+            .collect::<Vec<Interval>>(),
+    );
+
+    // Widest first for assignments starting on the same section, so an assignment can only be within one that comes
+    // before it in this order.
+    let mut by_start = (0..assignments.len()).collect::<Vec<usize>>();
+    by_start.sort_unstable_by_key(|&elf| {
+        (
+            assignments[elf].start,
+            std::cmp::Reverse(assignments[elf].end),
+            elf,
+        )
+    });
+
+    // Sweep keeping track of the assignment reaching furthest so far. Anything ending before it is within it.
+    let mut redundant = vec![];
+    let mut furthest: Option<usize> = None;
+    for &elf in &by_start {
+        match furthest {
+            Some(other) if assignments[other].end >= assignments[elf].end => {
+                redundant.push((elf, other))
+            }
+            _ => furthest = Some(elf),
+        }
+    }
+    redundant.sort_unstable();
+
+    // Greedily cover each stretch of sections: from the first uncovered section, take whichever assignment starting
+    // by then reaches furthest. Positions are u128s as the section after the last can be past u64::MAX.
+    let mut minimal_cover = vec![];
+    let mut candidates = by_start.iter().peekable();
+    for stretch in merge(assignments) {
+        let mut uncovered = stretch.start as u128;
+        while uncovered <= stretch.end as u128 {
+            let mut best: Option<usize> = None;
+            while let Some(&&elf) = candidates.peek() {
+                if assignments[elf].start as u128 > uncovered {
+                    break;
+                }
+                if best.is_none_or(|best| assignments[elf].end > assignments[best].end) {
+                    best = Some(elf);
+                }
+                candidates.next();
+            }
+            let best = best.expect("Every section in a stretch is covered");
+            minimal_cover.push(best);
+            uncovered = assignments[best].end as u128 + 1;
+        }
+    }
+
+    AssignmentAnalysis {
+        max_depth,
+        deepest_sections,
+        redundant,
+        minimal_cover,
+    }
+}
+
+pub fn parse_pair(line: &str) -> (Interval, Interval) {
+    let (elf1, elf2) = line
+        .split_once(',')
+        .unwrap_or_else(|| panic!("{} Should be a comma separated pair", line));
+    (parse_assignment(elf1), parse_assignment(elf2))
+}
+
+pub fn parse_assignment(assignment: &str) -> Interval {
+    let (start, end) = assignment.split_once('-').unwrap_or_else(|| {
+        panic!(
+            "Expected a '-' separated pair of intergers for {}",
+            assignment
+        )
+    });
+    let start = start
+        .parse::<u64>()
+        .unwrap_or_else(|_| panic!("Expected number not {}", start));
+    let end = end
+        .parse::<u64>()
+        .unwrap_or_else(|_| panic!("Expected number not {}", end));
+    Interval::new(start, end)
+        .unwrap_or_else(|| panic!("Assignment {} ends before it starts", assignment))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_day04_part1_case1() {
+        assert_eq!(
+            day04(
+                "2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8"
+            )
+            .0,
+            "2".to_string()
+        )
+    }
+
+    #[test]
+    fn check_day04_part2_case1() {
+        assert_eq!(day04("").1, "0".to_string())
+    }
+
+    #[test]
+    fn check_day04_both_case1() {
+        assert_eq!(
+            day04(
+                "2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8"
+            ),
+            ("2".to_string(), "4".to_string())
+        )
+    }
+
+    #[test]
+    fn check_day04_interval_operations() {
+        let interval = |start, end| Interval::new(start, end).unwrap();
+        assert_eq!(Interval::new(5, 4), None);
+        assert!(interval(2, 8).contains(interval(3, 7)));
+        assert!(!interval(3, 7).contains(interval(2, 8)));
+        assert!(interval(5, 7).overlaps(interval(7, 9)));
+        assert!(!interval(2, 3).overlaps(interval(4, 5)));
+        assert_eq!(
+            interval(2, 6).intersection(interval(4, 8)),
+            Some(interval(4, 6))
+        );
+        assert_eq!(interval(2, 3).intersection(interval(4, 5)), None);
+        assert_eq!(interval(2, 3).union(interval(4, 5)), Some(interval(2, 5)));
+        assert_eq!(interval(2, 3).union(interval(5, 5)), None);
+        assert_eq!(interval(0, u64::MAX).section_count(), u64::MAX as u128 + 1);
+    }
+
+    #[test]
+    fn check_day04_huge_ranges() {
+        assert_eq!(
+            day04("1-4000000000,2-3999999999\n1-2,3000000000-4000000000"),
+            ("1".to_string(), "1".to_string())
+        );
+    }
+
+    #[test]
+    fn check_day04_coverage_queries() {
+        let assignments = "2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8"
+            .lines()
+            .flat_map(|line| {
+                let (elf1, elf2) = parse_pair(line);
+                [elf1, elf2]
+            })
+            .collect::<Vec<Interval>>();
+        assert_eq!(total_covered(&assignments), 8);
+        assert_eq!(covered_exactly_once(&assignments), 1);
+        let edges = [
+            Interval::new(1, 3).unwrap(),
+            Interval::new(3, 4).unwrap(),
+            Interval::new(10, u64::MAX).unwrap(),
+        ];
+        assert_eq!(total_covered(&edges), 4 + (u64::MAX - 9) as u128);
+        assert_eq!(covered_exactly_once(&edges), 3 + (u64::MAX - 9) as u128);
+        assert_eq!(
+            coverage(&edges[..2]),
+            [
+                (Interval::new(1, 2).unwrap(), 1),
+                (Interval::new(3, 3).unwrap(), 2),
+                (Interval::new(4, 4).unwrap(), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn check_day04_assignment_analysis() {
+        let assignments = parse_assignments(
+            "2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8",
+        );
+        let analysis = analyse_assignments(&assignments);
+        assert_eq!(analysis.max_depth, 8);
+        assert_eq!(analysis.deepest_sections, [Interval::new(6, 6).unwrap()]);
+        // 2-8 (elf 7) covers everyone but 7-9 (elf 6), which runs past it.
+        assert_eq!(
+            analysis.redundant,
+            [
+                (0, 6),
+                (1, 6),
+                (2, 6),
+                (3, 6),
+                (4, 6),
+                (7, 6),
+                (8, 6),
+                (9, 6),
+                (10, 6),
+                (11, 6)
+            ]
+        );
+        assert_eq!(analysis.minimal_cover, [6, 5]);
+    }
+
+    #[test]
+    fn check_day04_minimal_cover() {
+        let assignments = parse_assignments(&format!(
+            "1-3,1-3\n2-5,4-7\n6-9,20-30\n25-{},30-40",
+            u64::MAX
+        ));
+        let analysis = analyse_assignments(&assignments);
+        assert_eq!(analysis.redundant, [(1, 0), (7, 6)]);
+        // 4-7 reaches further than 2-5, so 2-5 isn't needed.
+        assert_eq!(analysis.minimal_cover, [0, 3, 4, 5, 6]);
+        assert_eq!(analysis.max_depth, 3);
+        assert_eq!(
+            analysis.deepest_sections,
+            [Interval::new(2, 3).unwrap(), Interval::new(30, 30).unwrap()]
+        );
+    }
+}
@@ -0,0 +1,786 @@
+use std::collections::HashMap;
+
+use crate::simulation::Simulation;
+
+// Stacks of crates by their label, each listed bottom crate first.
+pub type Stacks = HashMap<usize, Vec<char>>;
+
+pub fn day05(input_lines: &str) -> (String, String) {
+    let (crates, instructions) = parse_input(input_lines);
+    let mut yard = CrateYard::new(crates, Box::new(CrateMover9000));
+    for instruction in &instructions {
+        yard.apply(instruction)
+            .unwrap_or_else(|error| panic!("{}", error));
+    }
+    let answer1 = read_diag(yard.stacks());
+    yard.replay_with(Box::new(CrateMover9001))
+        .unwrap_or_else(|error| panic!("{}", error));
+    let answer2 = read_diag(yard.stacks());
+    (answer1, answer2)
+}
+
+// `cargo run -- report 5 [crane]` shows what's on top of the stacks after rearranging them with another crane: 9000,
+// 9001, batch:<capacity>, bottom or swap.
+pub fn day05_report(input_lines: &str, args: &[String]) -> String {
+    let (crates, instructions) = parse_input(input_lines);
+    let crane: Box<dyn Crane> = match args.first().map(String::as_str) {
+        None | Some("9000") => Box::new(CrateMover9000),
+        Some("9001") => Box::new(CrateMover9001),
+        Some("bottom") => Box::new(BottomCrane),
+        Some("swap") => Box::new(SwapCrane),
+        Some(crane) => match crane.strip_prefix("batch:").map(str::parse::<usize>) {
+            Some(Ok(capacity)) => Box::new(BatchCrane { capacity }),
+            _ => panic!("Unknown crane {}", crane),
+        },
+    };
+    let mut yard = CrateYard::new(crates, crane);
+    for instruction in &instructions {
+        if let Err(error) = yard.apply(instruction) {
+            return format!(
+                "Stuck after {} instructions: {}\n{}\nTops of the stacks: {}\n",
+                yard.history().len(),
+                error,
+                render_diagram(yard.stacks()),
+                read_diag(yard.stacks())
+            );
+        }
+    }
+    format!(
+        "{}\nTops of the stacks: {}\n",
+        render_diagram(yard.stacks()),
+        read_diag(yard.stacks())
+    )
+}
+
+// `cargo run -- debug 5 [part]` follows the instructions one at a time with the part's crane, drawing the stacks after
+// each.
+pub fn day05_simulation(input_lines: &str, part: u8) -> Box<dyn Simulation> {
+    let (crates, instructions) = parse_input(input_lines);
+    let crane: Box<dyn Crane> = if part == 2 {
+        Box::new(CrateMover9001)
+    } else {
+        Box::new(CrateMover9000)
+    };
+    Box::new(Rearrangement {
+        yard: CrateYard::new(crates, crane),
+        instructions,
+        error: None,
+    })
+}
+
+fn parse_input(input_lines: &str) -> (Stacks, Vec<Instruction>) {
+    let (crate_diag, instructions) = input_lines.split_once("\n\n").unwrap();
+    let crates = parse_diagram(crate_diag);
+    let instructions = parse_instructions(instructions, crate_diag.lines().count() + 2)
+        .unwrap_or_else(|error| panic!("{}", error));
+    (crates, instructions)
+}
+
+// A yard part way through its instructions. Stops at the first instruction the crane can't follow.
+struct Rearrangement {
+    yard: CrateYard,
+    instructions: Vec<Instruction>,
+    error: Option<String>,
+}
+
+impl Simulation for Rearrangement {
+    fn step(&mut self) -> bool {
+        if self.error.is_some() {
+            return false;
+        }
+        let Some(instruction) = self.instructions.get(self.yard.history().len()) else {
+            return false;
+        };
+        if let Err(error) = self.yard.apply(instruction) {
+            self.error = Some(error.to_string());
+            return false;
+        }
+        true
+    }
+
+    fn snapshot(&self) -> String {
+        render_diagram(self.yard.stacks())
+    }
+
+    fn inspect(&self) -> Vec<(String, String)> {
+        let followed = self.yard.history().len();
+        let mut values = vec![
+            ("instructions".to_string(), followed.to_string()),
+            ("tops".to_string(), read_diag(self.yard.stacks())),
+        ];
+        if let Some(instruction) = self.instructions.get(followed) {
+            values.push((
+                "next".to_string(),
+                format!(
+                    "move {} from {} to {}",
+                    instruction.quantity, instruction.from, instruction.to
+                ),
+            ));
+        }
+        if let Some(error) = &self.error {
+            values.push(("error".to_string(), error.clone()));
+        }
+        values
+    }
+}
+
+// Read a drawing of the stacks like the puzzle's, with a row of stack labels under the crates. Each crate belongs to
+// the stack whose label is closest to it horizontally, so wide labels and stacks needn't line up exactly. Every
+// labelled stack is included, even if it's empty.
+pub fn parse_diagram(crate_diag: &str) -> Stacks {
+    let lines = crate_diag
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect::<Vec<&str>>();
+    let (label_line, crate_lines) = lines.split_last().expect("No crate diagram");
+    let labels = tokens(label_line)
+        .map(|(centre, label)| {
+            let label = label
+                .parse::<usize>()
+                .unwrap_or_else(|_| panic!("Expected a stack number, not {}", label));
+            (centre, label)
+        })
+        .collect::<Vec<(usize, usize)>>();
+
+    let mut crates: Stacks = labels.iter().map(|(_, label)| (*label, vec![])).collect();
+    for line in crate_lines.iter().rev() {
+        for (centre, token) in tokens(line) {
+            let mut chars = token.chars();
+            let (Some('['), Some(curr_crate), Some(']'), None) =
+                (chars.next(), chars.next(), chars.next(), chars.next())
+            else {
+                panic!("Expected a crate like [A], not {}", token);
+            };
+            let (_, label) = labels
+                .iter()
+                .min_by_key(|(label_centre, _)| label_centre.abs_diff(centre))
+                .expect("No stack labels");
+            crates.get_mut(label).unwrap().push(curr_crate);
+        }
+    }
+    crates
+}
+
+// The whitespace separated tokens in a line, along with the column of the middle of each, counting in characters
+// rather than bytes.
+fn tokens(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut column = 0;
+    line.split(' ').filter_map(move |token| {
+        let start = column;
+        let width = token.chars().count();
+        column += width + 1;
+        (width > 0).then_some((start + width / 2, token))
+    })
+}
+
+// Draw the stacks in the puzzle's format, which parse_diagram reads back. Stacks are drawn in label order, each as
+// wide as the longest label (or a crate, if that's wider), with a space between them.
+pub fn render_diagram(stacks: &Stacks) -> String {
+    let mut labels = stacks.keys().copied().collect::<Vec<usize>>();
+    labels.sort_unstable();
+    let width = labels
+        .iter()
+        .map(|label| label.to_string().len())
+        .max()
+        .unwrap_or(0)
+        .max(3);
+    let centred = |text: &str| {
+        let left = (width - text.chars().count()) / 2;
+        format!("{:left$}{:<rest$}", "", text, rest = width - left)
+    };
+    let height = stacks.values().map(Vec::len).max().unwrap_or(0);
+    let mut lines = (0..height)
+        .rev()
+        .map(|level| {
+            labels
+                .iter()
+                .map(|label| match stacks[label].get(level) {
+                    Some(curr_crate) => centred(&format!("[{}]", curr_crate)),
+                    None => centred(""),
+                })
+                .collect::<Vec<String>>()
+                .join(" ")
+        })
+        .collect::<Vec<String>>();
+    lines.push(
+        labels
+            .iter()
+            .map(|label| centred(&label.to_string()))
+            .collect::<Vec<String>>()
+            .join(" "),
+    );
+    lines.join("\n")
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Instruction {
+    // Where the instruction is in the puzzle input, for error messages.
+    pub line: usize,
+    pub quantity: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+// Instructions are numbered by line from first_line, which should be the line of the input they start on.
+pub fn parse_instructions(
+    instructions: &str,
+    first_line: usize,
+) -> Result<Vec<Instruction>, CraneError> {
+    let re = regex::Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap();
+    instructions
+        .lines()
+        .enumerate()
+        .filter(|(_, text)| !text.trim().is_empty())
+        .map(|(idx, text)| {
+            let line = first_line + idx;
+            let instruction =
+                re.captures(text.trim())
+                    .ok_or_else(|| CraneError::InvalidInstruction {
+                        line,
+                        text: text.to_string(),
+                    })?;
+            let number = |idx: usize| {
+                instruction[idx]
+                    .parse::<usize>()
+                    .map_err(|_| CraneError::InvalidInstruction {
+                        line,
+                        text: text.to_string(),
+                    })
+            };
+            Ok(Instruction {
+                line,
+                quantity: number(1)?,
+                from: number(2)?,
+                to: number(3)?,
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CraneError {
+    InvalidInstruction {
+        line: usize,
+        text: String,
+    },
+    UnknownStack {
+        line: usize,
+        stack: usize,
+    },
+    NotEnoughCrates {
+        line: usize,
+        stack: usize,
+        wanted: usize,
+        available: usize,
+    },
+    ZeroCapacity,
+}
+
+impl std::fmt::Display for CraneError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            CraneError::InvalidInstruction { line, text } => {
+                write!(f, "line {}: can't understand {:?}", line, text)
+            }
+            CraneError::UnknownStack { line, stack } => {
+                write!(f, "line {}: there's no stack {}", line, stack)
+            }
+            CraneError::NotEnoughCrates {
+                line,
+                stack,
+                wanted,
+                available,
+            } => write!(
+                f,
+                "line {}: can't move {} crates from stack {}, which only has {}",
+                line, wanted, stack, available
+            ),
+            CraneError::ZeroCapacity => {
+                write!(f, "a crane that can't lift anything can't move crates")
+            }
+        }
+    }
+}
+
+// A crane moves crates between two stacks. Implementations only need to rearrange the two stacks: checking the
+// stacks exist and recording history is done by CrateYard. When an instruction moves crates onto the stack they came
+// from, `to` starts empty and is put back on top of `from` afterwards.
+pub trait Crane {
+    fn move_crates(
+        &self,
+        instruction: &Instruction,
+        from: &mut Vec<char>,
+        to: &mut Vec<char>,
+    ) -> Result<(), CraneError>;
+}
+
+// Check the stack has enough crates for the instruction.
+fn check_height(
+    instruction: &Instruction,
+    stack: usize,
+    crates: &[char],
+) -> Result<(), CraneError> {
+    if crates.len() < instruction.quantity {
+        return Err(CraneError::NotEnoughCrates {
+            line: instruction.line,
+            stack,
+            wanted: instruction.quantity,
+            available: crates.len(),
+        });
+    }
+    Ok(())
+}
+
+// Moves crates one at a time, so they end up in reverse order.
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn move_crates(
+        &self,
+        instruction: &Instruction,
+        from: &mut Vec<char>,
+        to: &mut Vec<char>,
+    ) -> Result<(), CraneError> {
+        BatchCrane { capacity: 1 }.move_crates(instruction, from, to)
+    }
+}
+
+// Moves all the crates at once, so they keep their order.
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn move_crates(
+        &self,
+        instruction: &Instruction,
+        from: &mut Vec<char>,
+        to: &mut Vec<char>,
+    ) -> Result<(), CraneError> {
+        BatchCrane {
+            capacity: usize::MAX,
+        }
+        .move_crates(instruction, from, to)
+    }
+}
+
+// Moves up to `capacity` crates at a time, making as many trips as it needs.
+pub struct BatchCrane {
+    pub capacity: usize,
+}
+
+impl Crane for BatchCrane {
+    fn move_crates(
+        &self,
+        instruction: &Instruction,
+        from: &mut Vec<char>,
+        to: &mut Vec<char>,
+    ) -> Result<(), CraneError> {
+        if self.capacity == 0 {
+            return Err(CraneError::ZeroCapacity);
+        }
+        check_height(instruction, instruction.from, from)?;
+        let mut remaining = instruction.quantity;
+        while remaining > 0 {
+            let trip = remaining.min(self.capacity);
+            let lifted = from.split_off(from.len() - trip);
+            to.extend(lifted);
+            remaining -= trip;
+        }
+        Ok(())
+    }
+}
+
+// Slides crates out from the bottom of the stack, putting them on top of the other stack in the same order.
+pub struct BottomCrane;
+
+impl Crane for BottomCrane {
+    fn move_crates(
+        &self,
+        instruction: &Instruction,
+        from: &mut Vec<char>,
+        to: &mut Vec<char>,
+    ) -> Result<(), CraneError> {
+        check_height(instruction, instruction.from, from)?;
+        to.extend(from.drain(..instruction.quantity));
+        Ok(())
+    }
+}
+
+// Swaps the top crates of the two stacks, keeping the order of each group.
+pub struct SwapCrane;
+
+impl Crane for SwapCrane {
+    fn move_crates(
+        &self,
+        instruction: &Instruction,
+        from: &mut Vec<char>,
+        to: &mut Vec<char>,
+    ) -> Result<(), CraneError> {
+        check_height(instruction, instruction.from, from)?;
+        check_height(instruction, instruction.to, to)?;
+        let from_top = from.split_off(from.len() - instruction.quantity);
+        let to_top = to.split_off(to.len() - instruction.quantity);
+        from.extend(to_top);
+        to.extend(from_top);
+        Ok(())
+    }
+}
+
+// A step that's been taken, along with the two stacks as they were before it so it can be undone whatever the crane.
+struct Step {
+    instruction: Instruction,
+    before: [(usize, Vec<char>); 2],
+}
+
+// The stacks and the crane working on them, keeping a history of each instruction followed so they can be undone and
+// replayed.
+pub struct CrateYard {
+    stacks: Stacks,
+    crane: Box<dyn Crane>,
+    history: Vec<Step>,
+    // Steps that have been undone, most recently undone last.
+    undone: Vec<Instruction>,
+}
+
+impl CrateYard {
+    pub fn new(stacks: Stacks, crane: Box<dyn Crane>) -> Self {
+        Self {
+            stacks,
+            crane,
+            history: vec![],
+            undone: vec![],
+        }
+    }
+
+    pub fn stacks(&self) -> &Stacks {
+        &self.stacks
+    }
+
+    // The instructions followed so far, in order.
+    pub fn history(&self) -> Vec<&Instruction> {
+        self.history.iter().map(|step| &step.instruction).collect()
+    }
+
+    // Follow the instruction. If it can't be followed the stacks are left as they were. This forgets any undone
+    // steps, as they may no longer make sense.
+    pub fn apply(&mut self, instruction: &Instruction) -> Result<(), CraneError> {
+        self.follow(instruction)?;
+        self.undone.clear();
+        Ok(())
+    }
+
+    fn follow(&mut self, instruction: &Instruction) -> Result<(), CraneError> {
+        for stack in [instruction.from, instruction.to] {
+            if !self.stacks.contains_key(&stack) {
+                return Err(CraneError::UnknownStack {
+                    line: instruction.line,
+                    stack,
+                });
+            }
+        }
+        let before =
+            [instruction.from, instruction.to].map(|stack| (stack, self.stacks[&stack].clone()));
+        let result = if instruction.from == instruction.to {
+            // Lift the crates off onto the ground, then put them back.
+            let stack = self.stacks.get_mut(&instruction.from).unwrap();
+            let mut ground = vec![];
+            let result = self.crane.move_crates(instruction, stack, &mut ground);
+            stack.extend(ground);
+            result
+        } else {
+            let mut from = self.stacks.remove(&instruction.from).unwrap();
+            let to = self.stacks.get_mut(&instruction.to).unwrap();
+            let result = self.crane.move_crates(instruction, &mut from, to);
+            self.stacks.insert(instruction.from, from);
+            result
+        };
+        if let Err(error) = result {
+            self.restore(&before);
+            return Err(error);
+        }
+        self.history.push(Step {
+            instruction: instruction.clone(),
+            before,
+        });
+        Ok(())
+    }
+
+    fn restore(&mut self, before: &[(usize, Vec<char>); 2]) {
+        // Restore in reverse so if both are the same stack, the earliest copy wins.
+        for (stack, crates) in before.iter().rev() {
+            self.stacks.insert(*stack, crates.clone());
+        }
+    }
+
+    // Undo the last step, returning its instruction, or None if there's nothing to undo.
+    pub fn undo(&mut self) -> Option<Instruction> {
+        let step = self.history.pop()?;
+        self.restore(&step.before);
+        self.undone.push(step.instruction.clone());
+        Some(step.instruction)
+    }
+
+    // Follow the most recently undone instruction again, returning it, or None if nothing's been undone.
+    pub fn redo(&mut self) -> Result<Option<Instruction>, CraneError> {
+        let Some(instruction) = self.undone.pop() else {
+            return Ok(None);
+        };
+        if let Err(error) = self.follow(&instruction) {
+            self.undone.push(instruction);
+            return Err(error);
+        }
+        Ok(Some(instruction))
+    }
+
+    // Undo everything, then replay the history from the start with a different crane. Anything undone beforehand can
+    // still be redone afterwards.
+    pub fn replay_with(&mut self, crane: Box<dyn Crane>) -> Result<(), CraneError> {
+        let undone = std::mem::take(&mut self.undone);
+        while self.undo().is_some() {}
+        self.crane = crane;
+        let mut result = Ok(());
+        while !self.undone.is_empty() {
+            if let Err(error) = self.redo() {
+                result = Err(error);
+                break;
+            }
+        }
+        // Anything that couldn't be replayed goes back on top of what was undone before.
+        let not_replayed = std::mem::replace(&mut self.undone, undone);
+        self.undone.extend(not_replayed);
+        result
+    }
+}
+
+// The top crate of each stack, in label order. Empty stacks show as a space.
+pub fn read_diag(diag: &Stacks) -> String {
+    let mut labels = diag.keys().collect::<Vec<&usize>>();
+    labels.sort_unstable();
+    labels
+        .iter()
+        .map(|label| *diag[label].last().unwrap_or(&' '))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_yard(crane: Box<dyn Crane>) -> CrateYard {
+        CrateYard::new(
+            parse_diagram(
+                "    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 ",
+            ),
+            crane,
+        )
+    }
+
+    fn instruction(quantity: usize, from: usize, to: usize) -> Instruction {
+        Instruction {
+            line: 1,
+            quantity,
+            from,
+            to,
+        }
+    }
+
+    #[test]
+    fn check_day05_part1_case1() {
+        assert_eq!(
+            day05(
+                "    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2"
+            )
+            .0,
+            "CMZ".to_string()
+        )
+    }
+
+    #[test]
+    fn check_day05_part2_case1() {
+        assert_eq!(
+            day05(
+                "    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2"
+            )
+            .1,
+            "MCD".to_string()
+        )
+    }
+
+    #[test]
+    fn check_day05_both_case1() {
+        assert_eq!(
+            day05(
+                "    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2"
+            ),
+            ("CMZ".to_string(), "MCD".to_string())
+        )
+    }
+
+    #[test]
+    fn check_day05_cranes() {
+        let cases: [(Box<dyn Crane>, usize, &str); 5] = [
+            (Box::new(CrateMover9000), 2, "ZN M PDC"),
+            (Box::new(CrateMover9001), 2, "ZN M PCD"),
+            (Box::new(BatchCrane { capacity: 2 }), 2, "ZN M PCD"),
+            (Box::new(BottomCrane), 2, "ZN D PMC"),
+            (Box::new(SwapCrane), 1, "ZN MCP D"),
+        ];
+        for (crane, quantity, expected) in cases {
+            let mut yard = example_yard(crane);
+            yard.apply(&instruction(quantity, 2, 3)).unwrap();
+            let stacks = (1..=3)
+                .map(|stack| yard.stacks()[&stack].iter().collect::<String>())
+                .collect::<Vec<String>>()
+                .join(" ");
+            assert_eq!(stacks, expected);
+        }
+    }
+
+    #[test]
+    fn check_day05_batch_crane() {
+        let mut yard = example_yard(Box::new(BatchCrane { capacity: 2 }));
+        yard.apply(&instruction(1, 2, 1)).unwrap();
+        // Z N D on stack 1 goes over as N D, then Z.
+        yard.apply(&instruction(3, 1, 3)).unwrap();
+        assert_eq!(yard.stacks()[&3], ['P', 'N', 'D', 'Z']);
+        // Moving crates onto their own stack: D and Z come off in one trip and go back the same way.
+        yard.apply(&instruction(2, 3, 3)).unwrap();
+        assert_eq!(yard.stacks()[&3], ['P', 'N', 'D', 'Z']);
+        let mut yard = example_yard(Box::new(CrateMover9000));
+        yard.apply(&instruction(2, 2, 2)).unwrap();
+        assert_eq!(yard.stacks()[&2], ['M', 'D', 'C']);
+    }
+
+    #[test]
+    fn check_day05_invalid_moves() {
+        let mut yard = example_yard(Box::new(CrateMover9000));
+        assert_eq!(
+            yard.apply(&Instruction {
+                line: 7,
+                quantity: 4,
+                from: 2,
+                to: 1
+            }),
+            Err(CraneError::NotEnoughCrates {
+                line: 7,
+                stack: 2,
+                wanted: 4,
+                available: 3
+            })
+        );
+        assert_eq!(
+            yard.apply(&instruction(1, 2, 4)),
+            Err(CraneError::UnknownStack { line: 1, stack: 4 })
+        );
+        // Failed instructions leave the stacks alone and aren't recorded.
+        assert_eq!(read_diag(yard.stacks()), "NDP");
+        assert!(yard.history().is_empty());
+        assert_eq!(
+            parse_instructions("move 1 from 2 to 1\nmove one from 1 to 3\n", 5),
+            Err(CraneError::InvalidInstruction {
+                line: 6,
+                text: "move one from 1 to 3".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn check_day05_undo_and_replay() {
+        let instructions = parse_instructions(
+            "move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2",
+            6,
+        )
+        .unwrap();
+        let mut yard = example_yard(Box::new(CrateMover9000));
+        for instruction in &instructions {
+            yard.apply(instruction).unwrap();
+        }
+        assert_eq!(read_diag(yard.stacks()), "CMZ");
+        assert_eq!(yard.undo(), Some(instructions[3].clone()));
+        assert_eq!(yard.undo(), Some(instructions[2].clone()));
+        assert_eq!(read_diag(yard.stacks()), " CZ");
+        assert_eq!(yard.redo().unwrap(), Some(instructions[2].clone()));
+        assert_eq!(yard.history().len(), 3);
+        yard.replay_with(Box::new(CrateMover9001)).unwrap();
+        assert_eq!(yard.history().len(), 3);
+        assert_eq!(yard.redo().unwrap(), Some(instructions[3].clone()));
+        assert_eq!(read_diag(yard.stacks()), "MCD");
+        assert_eq!(yard.redo().unwrap(), None);
+    }
+
+    #[test]
+    fn check_day05_render_diagram() {
+        let diagram = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 ";
+        assert_eq!(render_diagram(&parse_diagram(diagram)), diagram);
+
+        let mut stacks: Stacks = (1..=12).map(|label| (label, vec![])).collect();
+        stacks.insert(1, vec!['A', 'B']);
+        stacks.insert(9, vec!['C']);
+        stacks.insert(10, vec!['D', 'E', 'F']);
+        stacks.insert(12, vec!['G']);
+        let rendered = render_diagram(&stacks);
+        assert_eq!(
+            rendered.lines().last(),
+            Some(" 1   2   3   4   5   6   7   8   9  10  11  12 ")
+        );
+        assert_eq!(parse_diagram(&rendered), stacks);
+
+        let hand_drawn = "        [F]\n[B]     [E]\n[A] [C] [D]  [G]\n 1   9  10    12\n";
+        let expected: Stacks = [
+            (1, vec!['A', 'B']),
+            (9, vec!['C']),
+            (10, vec!['D', 'E', 'F']),
+            (12, vec!['G']),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(parse_diagram(hand_drawn), expected);
+    }
+
+    #[test]
+    fn check_day05_simulation() {
+        let mut simulation = day05_simulation(
+            "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\nmove 3 from 1 to 3",
+            1,
+        );
+        assert!(simulation.step());
+        assert_eq!(
+            simulation.snapshot(),
+            "[D]        \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 "
+        );
+        assert!(simulation.step());
+        assert!(!simulation.step());
+        assert!(simulation
+            .inspect()
+            .contains(&("tops".to_string(), " CZ".to_string())));
+    }
+}
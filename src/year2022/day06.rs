@@ -0,0 +1,168 @@
+use std::io::{self, Read};
+
+pub fn day06(input_lines: &str) -> (String, String) {
+    let markers = find_markers(input_lines.as_bytes(), &[4, 14])
+        .unwrap_or_else(|error| panic!("Couldn't read the signal: {}", error));
+    let answer1 = markers[0].expect("No start-of-packet marker");
+    let answer2 = markers[1].expect("No start-of-message marker");
+    (format!("{}", answer1), format!("{}", answer2))
+}
+
+// Scan a signal for markers of each of the given lengths at once, reading only as far as the last one to be found.
+// Returns the position just after each marker, in the same order as the lengths.
+pub fn find_markers(mut signal: impl Read, lengths: &[usize]) -> io::Result<Vec<Option<usize>>> {
+    let mut detector = MarkerDetector::new(lengths);
+    let mut buffer = [0; 8192];
+    while !detector.done() {
+        let read = match signal.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        };
+        for byte in &buffer[..read] {
+            if detector.done() {
+                break;
+            }
+            detector.push(*byte);
+        }
+    }
+    Ok(lengths
+        .iter()
+        .map(|length| detector.marker(*length))
+        .collect())
+}
+
+// Tracks the longest run of distinct bytes ending at the latest one, by remembering where each byte value was last
+// seen. The run only ever grows one byte at a time, so markers are found shortest first and each push only needs to
+// check the shortest length not yet found.
+pub struct MarkerDetector {
+    // Lengths not yet found, longest first.
+    pending: Vec<usize>,
+    found: Vec<(usize, usize)>,
+    // One past the position each byte value was last seen at, or 0 if it hasn't been.
+    last_seen: [usize; 256],
+    run_start: usize,
+    position: usize,
+}
+
+impl MarkerDetector {
+    pub fn new(lengths: &[usize]) -> Self {
+        let mut pending = lengths.to_vec();
+        pending.sort_unstable_by(|a, b| b.cmp(a));
+        pending.dedup();
+        let mut detector = Self {
+            pending,
+            found: vec![],
+            last_seen: [0; 256],
+            run_start: 0,
+            position: 0,
+        };
+        detector.record_markers();
+        detector
+    }
+
+    pub fn push(&mut self, byte: u8) {
+        self.run_start = self.run_start.max(self.last_seen[byte as usize]);
+        self.position += 1;
+        self.last_seen[byte as usize] = self.position;
+        self.record_markers();
+    }
+
+    // Whether every length has been found, so there's no need to read further.
+    pub fn done(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    pub fn marker(&self, length: usize) -> Option<usize> {
+        self.found
+            .iter()
+            .find(|(found_length, _)| *found_length == length)
+            .map(|(_, position)| *position)
+    }
+
+    fn record_markers(&mut self) {
+        while self.pending.last() == Some(&(self.position - self.run_start)) {
+            let length = self.pending.pop().unwrap();
+            self.found.push((length, self.position));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_day06_part1_case1() {
+        assert_eq!(day06("bvwbjplbgvbhsrlpgdmjqwftvncz").0, "5".to_string())
+    }
+    #[test]
+    fn check_day06_part1_case2() {
+        assert_eq!(day06("nppdvjthqldpwncqszvftbrmjlhg").0, "6".to_string())
+    }
+    #[test]
+    fn check_day06_part1_case3() {
+        assert_eq!(
+            day06("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg").0,
+            "10".to_string()
+        )
+    }
+    #[test]
+    fn check_day06_part1_case4() {
+        assert_eq!(
+            day06("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw").0,
+            "11".to_string()
+        )
+    }
+    #[test]
+    fn check_day06_part2_case1() {
+        assert_eq!(day06("mjqjpqmgbljsphdztnvjfqwrcgsmlb").1, "19".to_string())
+    }
+    #[test]
+    fn check_day06_part2_case2() {
+        assert_eq!(day06("bvwbjplbgvbhsrlpgdmjqwftvncz").1, "23".to_string())
+    }
+    #[test]
+    fn check_day06_part2_case3() {
+        assert_eq!(day06("nppdvjthqldpwncqszvftbrmjlhg").1, "23".to_string())
+    }
+    #[test]
+    fn check_day06_part2_case4() {
+        assert_eq!(
+            day06("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg").1,
+            "29".to_string()
+        )
+    }
+    #[test]
+    fn check_day06_part2_case5() {
+        assert_eq!(
+            day06("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw").1,
+            "26".to_string()
+        )
+    }
+
+    #[test]
+    fn check_day06_both_case1() {
+        assert_eq!(
+            day06("bvwbjplbgvbhsrlpgdmjqwftvncz"),
+            ("5".to_string(), "23".to_string())
+        )
+    }
+
+    #[test]
+    fn check_day06_find_markers() {
+        let signal = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+        assert_eq!(
+            find_markers(signal.as_bytes(), &[14, 4, 1, 0, 4, 27]).unwrap(),
+            vec![Some(19), Some(7), Some(1), Some(0), Some(7), None]
+        );
+        assert_eq!(find_markers("aaaa".as_bytes(), &[2]).unwrap(), vec![None]);
+        assert_eq!(find_markers("".as_bytes(), &[1]).unwrap(), vec![None]);
+
+        // Any byte counts as a character, and markers can span separate reads.
+        let bytes = [0xff, 0x00, 0xff, 0x80, 0x00, 0x01];
+        let chunked = (&bytes[..2]).chain(&bytes[2..4]).chain(&bytes[4..]);
+        assert_eq!(find_markers(chunked, &[3]).unwrap(), vec![Some(4)]);
+    }
+}
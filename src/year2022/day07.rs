@@ -0,0 +1,1131 @@
+use std::fmt;
+
+use nom::character::complete::{alpha1, char, space0};
+use nom::IResult;
+use serde::Serialize;
+
+pub fn day07(input_lines: &str) -> (String, String) {
+    let root = interpret_session(input_lines).unwrap_or_else(|error| panic!("{}", error));
+    let answer1 = filter_tree(&root, |node| node.deep_size < 100000)
+        .iter()
+        .map(|node| node.deep_size)
+        .sum::<u64>();
+
+    let to_free = (root.deep_size + 30000000).saturating_sub(70000000);
+    let answer2 = filter_tree(&root, |node: &Node| node.deep_size >= to_free)
+        .iter()
+        .map(|node| node.deep_size)
+        .min()
+        .unwrap();
+    (format!("{}", answer1), format!("{}", answer2))
+}
+
+// `cargo run -- report 7 [query]` answers questions about the filesystem. The query is one of `du [depth]`, `tree`,
+// `largest [n]` (the default, for the 5 largest directories and files), `find <glob>`, `path <path>`, `json` or
+// `plan [least|fewest] [<capacity> <required free>] [protected path...]`, which plans which directories to delete to
+// free up space (on the puzzle's disk unless told otherwise).
+pub fn day07_report(input_lines: &str, args: &[String]) -> String {
+    let root = interpret_session(input_lines).unwrap_or_else(|error| panic!("{}", error));
+    let arg = args.get(1).map(String::as_str);
+    match args.first().map(String::as_str) {
+        Some("du") => root.du_report(arg.map(|depth| {
+            depth
+                .parse::<usize>()
+                .unwrap_or_else(|_| panic!("Expected a depth, not {}", depth))
+        })),
+        Some("tree") => root.tree_report(),
+        None | Some("largest") => {
+            let n = arg.map_or(5, |n| {
+                n.parse::<usize>()
+                    .unwrap_or_else(|_| panic!("Expected a count, not {}", n))
+            });
+            let mut report = String::from("Largest directories:\n");
+            for dir in root.largest_directories(n) {
+                report.push_str(&format!("{:>12}  {}\n", dir.deep_size, dir.path));
+            }
+            report.push_str("Largest files:\n");
+            for file in root.largest_files(n) {
+                report.push_str(&format!("{:>12}  {}\n", file.size, file.path));
+            }
+            report
+        }
+        Some("find") => {
+            let pattern = arg.expect("Please provide a glob to match files against.");
+            root.matching_files(pattern)
+                .iter()
+                .map(|file| format!("{:>12}  {}\n", file.size, file.path))
+                .collect()
+        }
+        Some("path") => match root.lookup(arg.expect("Please provide a path to look up.")) {
+            Some(Entry::Directory(dir)) => format!(
+                "{}: directory of {} bytes ({} directly), {} directories, {} files\n",
+                dir.path,
+                dir.deep_size,
+                dir.shallow_size,
+                dir.children.len(),
+                dir.files.len()
+            ),
+            Some(Entry::File(file)) => format!("{}: file of {} bytes\n", file.path, file.size),
+            None => "No such file or directory\n".to_string(),
+        },
+        Some("json") => root.to_json() + "\n",
+        Some("plan") => {
+            let (goal, mut args) = match args.get(1).map(String::as_str) {
+                Some("fewest") => (PlanGoal::FewestDeletions, &args[2..]),
+                Some("least") => (PlanGoal::LeastData, &args[2..]),
+                _ => (PlanGoal::LeastData, &args[1..]),
+            };
+            let (mut capacity, mut required_free) = (70000000, 30000000);
+            if let [first, second, ..] = args {
+                if let (Ok(first), Ok(second)) = (first.parse::<u64>(), second.parse::<u64>()) {
+                    (capacity, required_free) = (first, second);
+                    args = &args[2..];
+                }
+            }
+            let protected = args.iter().map(String::as_str).collect::<Vec<&str>>();
+            match root.plan_cleanup(capacity, required_free, &protected, goal) {
+                Ok(plan) => plan.explain(),
+                Err(error) => format!("No plan: {}\n", error),
+            }
+        }
+        Some(query) => panic!("Unknown query {}", query),
+    }
+}
+
+fn filter_tree(node: &Node, filter_condition: impl Fn(&Node) -> bool) -> Vec<&Node> {
+    let mut matching_nodes = vec![];
+    let mut testing_nodes = vec![node];
+    while !testing_nodes.is_empty() {
+        matching_nodes.extend(testing_nodes.iter().filter(|node| filter_condition(node)));
+        testing_nodes = testing_nodes
+            .iter()
+            .flat_map(|node| &node.children)
+            .collect();
+    }
+    matching_nodes
+}
+
+// Build the filesystem from a whole session, which may move around the tree freely, revisit directories and list
+// them more than once. The session starts at the root.
+pub fn interpret_session(transcript: &str) -> Result<Node, ShellError> {
+    let mut shell = Shell::new();
+    for (line_idx, line) in transcript.lines().enumerate() {
+        shell.execute(line_idx + 1, line.trim())?;
+    }
+    Ok(shell.into_tree())
+}
+
+#[derive(Debug)]
+pub enum ShellError {
+    UnknownCommand { line: usize, command: String },
+    InvalidCommand { line: usize, text: String },
+    UnexpectedOutput { line: usize, text: String },
+    InvalidOutput { line: usize, text: String },
+    AboveRoot { line: usize },
+}
+
+impl fmt::Display for ShellError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ShellError::UnknownCommand { line, command } => {
+                write!(f, "Line {}: unknown command {}", line, command)
+            }
+            ShellError::InvalidCommand { line, text } => {
+                write!(f, "Line {}: couldn't understand command {}", line, text)
+            }
+            ShellError::UnexpectedOutput { line, text } => {
+                write!(f, "Line {}: output {} doesn't follow an ls", line, text)
+            }
+            ShellError::InvalidOutput { line, text } => {
+                write!(
+                    f,
+                    "Line {}: expected a file or directory, not {}",
+                    line, text
+                )
+            }
+            ShellError::AboveRoot { line } => {
+                write!(f, "Line {}: can't cd above the root directory", line)
+            }
+        }
+    }
+}
+
+enum Command<'a> {
+    Cd(&'a str),
+    Ls,
+}
+
+impl Command<'_> {
+    fn parse_from_line(line: &str) -> IResult<&str, (&str, &str)> {
+        let (line, _) = char('$')(line)?;
+        let (line, _) = space0(line)?;
+        let (line, name) = alpha1(line)?;
+        let (line, _) = space0(line)?;
+        Ok(("", (name, line.trim())))
+    }
+
+    fn from_line(line_no: usize, line: &str) -> Result<Command<'_>, ShellError> {
+        let invalid = || ShellError::InvalidCommand {
+            line: line_no,
+            text: line.to_string(),
+        };
+        let (_, (name, arg)) = Command::parse_from_line(line).map_err(|_| invalid())?;
+        match (name, arg) {
+            ("cd", "") => Err(invalid()),
+            ("cd", arg) => Ok(Command::Cd(arg)),
+            ("ls", "") => Ok(Command::Ls),
+            ("ls", _) => Err(invalid()),
+            (name, _) => Err(ShellError::UnknownCommand {
+                line: line_no,
+                command: name.to_string(),
+            }),
+        }
+    }
+}
+
+// Follows a session line by line, keeping track of the current directory as the path of names down from the root.
+struct Shell {
+    root: Node,
+    cwd: Vec<String>,
+    listing: bool,
+}
+
+impl Shell {
+    fn new() -> Self {
+        Shell {
+            root: Node::new_empty("/", "/"),
+            cwd: vec![],
+            listing: false,
+        }
+    }
+
+    fn execute(&mut self, line_no: usize, line: &str) -> Result<(), ShellError> {
+        if line.is_empty() {
+            return Ok(());
+        }
+        if !line.starts_with('$') {
+            if !self.listing {
+                return Err(ShellError::UnexpectedOutput {
+                    line: line_no,
+                    text: line.to_string(),
+                });
+            }
+            return self.record_listing(line_no, line);
+        }
+        self.listing = false;
+        match Command::from_line(line_no, line)? {
+            Command::Ls => {
+                // Listing a directory again replaces what we knew about its files.
+                let dir = self.current_dir();
+                dir.files.clear();
+                dir.shallow_size = 0;
+                self.listing = true;
+            }
+            Command::Cd(path) => {
+                if path.starts_with('/') {
+                    self.cwd.clear();
+                }
+                for name in path.split('/').filter(|name| !name.is_empty()) {
+                    match name {
+                        "." => {}
+                        ".." => {
+                            self.cwd
+                                .pop()
+                                .ok_or(ShellError::AboveRoot { line: line_no })?;
+                        }
+                        _ => {
+                            // We may not have listed the directory yet, so cd'ing into it is enough to know it exists.
+                            self.current_dir().child_mut(name);
+                            self.cwd.push(name.to_string());
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn record_listing(&mut self, line_no: usize, line: &str) -> Result<(), ShellError> {
+        let invalid = || ShellError::InvalidOutput {
+            line: line_no,
+            text: line.to_string(),
+        };
+        let (info, name) = line.split_once(' ').ok_or_else(invalid)?;
+        let name = name.trim();
+        if name.is_empty() || name.contains('/') {
+            return Err(invalid());
+        }
+        if info == "dir" {
+            self.current_dir().child_mut(name);
+        } else {
+            let size = info.parse::<u64>().map_err(|_| invalid())?;
+            self.current_dir().add_file(name, size);
+        }
+        Ok(())
+    }
+
+    fn current_dir(&mut self) -> &mut Node {
+        self.cwd
+            .iter()
+            .fold(&mut self.root, |node, name| node.child_mut(name))
+    }
+
+    fn into_tree(mut self) -> Node {
+        self.root.update_deep_size();
+        self.root
+    }
+}
+
+// A directory. Sizes are in bytes, shallow_size counting only the files directly inside it and deep_size everything
+// below it too.
+#[derive(Serialize)]
+pub struct Node {
+    name: String,
+    path: String,
+    shallow_size: u64,
+    deep_size: u64,
+    files: Vec<File>,
+    children: Vec<Node>,
+}
+
+#[derive(Serialize)]
+pub struct File {
+    name: String,
+    path: String,
+    size: u64,
+}
+
+pub enum Entry<'a> {
+    Directory(&'a Node),
+    File(&'a File),
+}
+
+impl Node {
+    fn new_empty(name: &str, path: &str) -> Self {
+        Node {
+            name: name.to_string(),
+            path: path.to_string(),
+            shallow_size: 0,
+            deep_size: 0,
+            files: vec![],
+            children: vec![],
+        }
+    }
+
+    // The child directory with this name, adding it if we haven't seen it before.
+    fn child_mut(&mut self, name: &str) -> &mut Node {
+        let idx = match self.children.iter().position(|child| child.name == name) {
+            Some(idx) => idx,
+            None => {
+                let path = join_path(&self.path, name);
+                self.children.push(Node::new_empty(name, &path));
+                self.children.len() - 1
+            }
+        };
+        &mut self.children[idx]
+    }
+
+    fn add_file(&mut self, name: &str, size: u64) {
+        if let Some(idx) = self.files.iter().position(|file| file.name == name) {
+            self.shallow_size -= self.files.remove(idx).size;
+        }
+        self.files.push(File {
+            name: name.to_string(),
+            path: join_path(&self.path, name),
+            size,
+        });
+        self.shallow_size += size;
+    }
+
+    fn update_deep_size(&mut self) -> u64 {
+        self.deep_size = self.shallow_size
+            + self
+                .children
+                .iter_mut()
+                .map(Node::update_deep_size)
+                .sum::<u64>();
+        self.deep_size
+    }
+
+    // The file or directory at an absolute path, like /a/e or /a/e/i. Trailing slashes are ignored.
+    pub fn lookup(&self, path: &str) -> Option<Entry<'_>> {
+        let path = path.strip_prefix('/')?;
+        let mut names = path.split('/').filter(|name| !name.is_empty()).peekable();
+        let mut node = self;
+        while let Some(name) = names.next() {
+            if let Some(child) = node.children.iter().find(|child| child.name == name) {
+                node = child;
+            } else if names.peek().is_none() {
+                return node
+                    .files
+                    .iter()
+                    .find(|file| file.name == name)
+                    .map(Entry::File);
+            } else {
+                return None;
+            }
+        }
+        Some(Entry::Directory(node))
+    }
+
+    // Every directory from this one down, parents before their children.
+    pub fn directories(&self) -> Vec<&Node> {
+        let mut directories = vec![self];
+        let mut idx = 0;
+        while idx < directories.len() {
+            directories.extend(&directories[idx].children);
+            idx += 1;
+        }
+        directories
+    }
+
+    pub fn files(&self) -> impl Iterator<Item = &File> {
+        self.directories().into_iter().flat_map(|dir| &dir.files)
+    }
+
+    // The n largest directories (by deep size) and files, largest first. Ties are broken by path.
+    pub fn largest_directories(&self, n: usize) -> Vec<&Node> {
+        let mut directories = self.directories();
+        directories.sort_by(|a, b| b.deep_size.cmp(&a.deep_size).then(a.path.cmp(&b.path)));
+        directories.truncate(n);
+        directories
+    }
+
+    pub fn largest_files(&self, n: usize) -> Vec<&File> {
+        let mut files = self.files().collect::<Vec<&File>>();
+        files.sort_by(|a, b| b.size.cmp(&a.size).then(a.path.cmp(&b.path)));
+        files.truncate(n);
+        files
+    }
+
+    // Files matching a glob pattern, in path order. Patterns with a / are matched against the whole path, others just
+    // against the file name.
+    pub fn matching_files(&self, pattern: &str) -> Vec<&File> {
+        let mut files = self
+            .files()
+            .filter(|file| match pattern.contains('/') {
+                true => glob_match(pattern.as_bytes(), file.path.as_bytes()),
+                false => glob_match(pattern.as_bytes(), file.name.as_bytes()),
+            })
+            .collect::<Vec<&File>>();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        files
+    }
+
+    // Like `du`: the deep size of each directory, children before their parents, down to max_depth levels below this
+    // one if given.
+    pub fn du_report(&self, max_depth: Option<usize>) -> String {
+        let mut report = String::new();
+        self.write_du(0, max_depth, &mut report);
+        report
+    }
+
+    fn write_du(&self, depth: usize, max_depth: Option<usize>, report: &mut String) {
+        if max_depth.is_some_and(|max_depth| depth > max_depth) {
+            return;
+        }
+        for child in &self.children {
+            child.write_du(depth + 1, max_depth, report);
+        }
+        report.push_str(&format!("{}\t{}\n", self.deep_size, self.path));
+    }
+
+    // Like `tree`: everything below this directory, with sizes, each level sorted by name.
+    pub fn tree_report(&self) -> String {
+        let mut report = format!("{} ({})\n", self.path, self.deep_size);
+        self.write_tree("", &mut report);
+        report
+    }
+
+    fn write_tree(&self, indent: &str, report: &mut String) {
+        let mut entries = self
+            .children
+            .iter()
+            .map(|child| (child.name.as_str(), child.deep_size, Some(child)))
+            .chain(
+                self.files
+                    .iter()
+                    .map(|file| (file.name.as_str(), file.size, None)),
+            )
+            .collect::<Vec<(&str, u64, Option<&Node>)>>();
+        entries.sort_by_key(|(name, _, _)| *name);
+        for (idx, (name, size, child)) in entries.iter().enumerate() {
+            let last = idx + 1 == entries.len();
+            let (branch, next_indent) = if last {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+            report.push_str(&format!("{}{}{} ({})\n", indent, branch, name, size));
+            if let Some(child) = child {
+                child.write_tree(&format!("{}{}", indent, next_indent), report);
+            }
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+fn join_path(dir: &str, name: &str) -> String {
+    format!("{}/{}", dir.trim_end_matches('/'), name)
+}
+
+// Shell-style glob matching: `*` matches any run of characters other than /, `**` any run at all and `?` any single
+// character other than /.
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern {
+        [] => text.is_empty(),
+        [b'*', b'*', rest @ ..] => (0..=text.len()).any(|skip| glob_match(rest, &text[skip..])),
+        [b'*', rest @ ..] => {
+            let segment = text.iter().position(|c| *c == b'/').unwrap_or(text.len());
+            (0..=segment).any(|skip| glob_match(rest, &text[skip..]))
+        }
+        [b'?', rest @ ..] => {
+            matches!(text.first(), Some(c) if *c != b'/') && glob_match(rest, &text[1..])
+        }
+        [c, rest @ ..] => text.first() == Some(c) && glob_match(rest, &text[1..]),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PlanGoal {
+    LeastData,
+    // Ties between plans with the same number of deletions go to the one deleting least data.
+    FewestDeletions,
+}
+
+#[derive(Debug)]
+pub enum PlanError {
+    UnknownProtectedPath(String),
+    NotEnoughSpace { needed: u64, deletable: u64 },
+}
+
+impl fmt::Display for PlanError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PlanError::UnknownProtectedPath(path) => {
+                write!(f, "protected path {} doesn't exist", path)
+            }
+            PlanError::NotEnoughSpace { needed, deletable } => write!(
+                f,
+                "need to free {} bytes but only {} can be deleted",
+                needed, deletable
+            ),
+        }
+    }
+}
+
+pub struct CleanupPlan<'a> {
+    pub goal: PlanGoal,
+    pub capacity: u64,
+    pub required_free: u64,
+    pub used: u64,
+    pub needed: u64,
+    pub protected: Vec<String>,
+    pub deletions: Vec<&'a Node>,
+}
+
+impl CleanupPlan<'_> {
+    pub fn freed(&self) -> u64 {
+        self.deletions.iter().map(|dir| dir.deep_size).sum()
+    }
+
+    pub fn explain(&self) -> String {
+        let free = self.capacity.saturating_sub(self.used);
+        let mut explanation = format!(
+            "{} of {} bytes are free and {} need to be, so {} bytes must be deleted.\n",
+            free, self.capacity, self.required_free, self.needed
+        );
+        if !self.protected.is_empty() {
+            explanation.push_str(&format!(
+                "Nothing in or containing {} can be deleted.\n",
+                self.protected.join(", ")
+            ));
+        }
+        let goal = match self.goal {
+            PlanGoal::LeastData => "the least data",
+            PlanGoal::FewestDeletions => "the fewest deletions",
+        };
+        explanation.push_str(&format!(
+            "Aiming for {}, deleting {} {} frees {} bytes, leaving {} free:\n",
+            goal,
+            self.deletions.len(),
+            if self.deletions.len() == 1 {
+                "directory"
+            } else {
+                "directories"
+            },
+            self.freed(),
+            free + self.freed()
+        ));
+        for dir in &self.deletions {
+            explanation.push_str(&format!("{:>12}  {}\n", dir.deep_size, dir.path));
+        }
+        explanation
+    }
+}
+
+impl Node {
+    // Choose directories to delete to bring the free space on a disk of the given capacity up to required_free. No
+    // directory in the plan contains another, and the root, the protected paths and anything containing or inside them
+    // are left alone.
+    pub fn plan_cleanup(
+        &self,
+        capacity: u64,
+        required_free: u64,
+        protected: &[&str],
+        goal: PlanGoal,
+    ) -> Result<CleanupPlan<'_>, PlanError> {
+        for path in protected {
+            if self.lookup(path).is_none() {
+                return Err(PlanError::UnknownProtectedPath(path.to_string()));
+            }
+        }
+        let candidates = Candidates::from_tree(self, protected);
+        let needed = (self.deep_size + required_free).saturating_sub(capacity);
+        let deletable = candidates.most_deletable();
+        if deletable < needed {
+            return Err(PlanError::NotEnoughSpace { needed, deletable });
+        }
+        let chosen = match (needed, goal) {
+            (0, _) => vec![],
+            (_, PlanGoal::LeastData) => candidates.least_data(needed),
+            (_, PlanGoal::FewestDeletions) => candidates.fewest_deletions(needed),
+        };
+        let mut deletions = chosen
+            .into_iter()
+            .map(|idx| candidates.dirs[idx])
+            .collect::<Vec<&Node>>();
+        deletions.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(CleanupPlan {
+            goal,
+            capacity,
+            required_free,
+            used: self.deep_size,
+            needed,
+            protected: protected.iter().map(|path| path.to_string()).collect(),
+            deletions,
+        })
+    }
+}
+
+// The directories below the root in depth-first order, so each directory's subtree is the run of positions from it up
+// to (but not including) its end. Choosing a directory then means jumping to its end, which makes it impossible to
+// choose anything nested in it.
+struct Candidates<'a> {
+    dirs: Vec<&'a Node>,
+    ends: Vec<usize>,
+    deletable: Vec<bool>,
+}
+
+impl<'a> Candidates<'a> {
+    fn from_tree(root: &'a Node, protected: &[&str]) -> Self {
+        let mut candidates = Candidates {
+            dirs: vec![],
+            ends: vec![],
+            deletable: vec![],
+        };
+        for child in &root.children {
+            candidates.add(child, protected);
+        }
+        candidates
+    }
+
+    fn add(&mut self, dir: &'a Node, protected: &[&str]) {
+        let idx = self.dirs.len();
+        let related = |path: &str| {
+            let path = path.trim_end_matches('/');
+            is_within(path, &dir.path) || is_within(&dir.path, path)
+        };
+        self.dirs.push(dir);
+        self.ends.push(0);
+        self.deletable
+            .push(!protected.iter().any(|path| related(path)));
+        for child in &dir.children {
+            self.add(child, protected);
+        }
+        self.ends[idx] = self.dirs.len();
+    }
+
+    fn size(&self, idx: usize) -> u64 {
+        self.dirs[idx].deep_size
+    }
+
+    // The most that can be freed, by deleting every outermost deletable directory.
+    fn most_deletable(&self) -> u64 {
+        let mut total = 0;
+        let mut idx = 0;
+        while idx < self.dirs.len() {
+            if self.deletable[idx] {
+                total += self.size(idx);
+                idx = self.ends[idx];
+            } else {
+                idx += 1;
+            }
+        }
+        total
+    }
+
+    // most[idx] is the most that can be freed by deleting directories from idx onwards, which bounds a search for the
+    // deletions freeing the least data.
+    fn least_data(&self, needed: u64) -> Vec<usize> {
+        let count = self.dirs.len();
+        let mut most = vec![0; count + 1];
+        for idx in (0..count).rev() {
+            most[idx] = most[idx + 1];
+            if self.deletable[idx] {
+                most[idx] = most[idx].max(self.size(idx) + most[self.ends[idx]]);
+            }
+        }
+        let mut search = DeletionSearch {
+            candidates: self,
+            needed,
+            most: &[most],
+            chosen: vec![],
+            best: (u64::MAX, vec![]),
+        };
+        search.search(0, None, 0);
+        search.best.1
+    }
+
+    // most[r][idx] is the most that can be freed by deleting at most r directories from idx onwards. The fewest
+    // deletions needed is the first r that frees enough from the start, and those tables then bound a search for the
+    // r deletions freeing the least data.
+    fn fewest_deletions(&self, needed: u64) -> Vec<usize> {
+        let count = self.dirs.len();
+        let mut most = vec![vec![0; count + 1]];
+        while most.last().unwrap()[0] < needed {
+            let fewer = most.last().unwrap();
+            let mut next = vec![0; count + 1];
+            for idx in (0..count).rev() {
+                next[idx] = next[idx + 1];
+                if self.deletable[idx] {
+                    next[idx] = next[idx].max(self.size(idx) + fewer[self.ends[idx]]);
+                }
+            }
+            most.push(next);
+        }
+        let mut search = DeletionSearch {
+            candidates: self,
+            needed,
+            most: &most,
+            chosen: vec![],
+            best: (u64::MAX, vec![]),
+        };
+        search.search(0, Some(most.len() - 1), 0);
+        search.best.1
+    }
+}
+
+// Depth-first search for the deletions freeing the least data. With a limit on the remaining deletions the tables give
+// the most that can be freed with that many, otherwise there's a single table for any number of them.
+struct DeletionSearch<'a> {
+    candidates: &'a Candidates<'a>,
+    needed: u64,
+    most: &'a [Vec<u64>],
+    chosen: Vec<usize>,
+    best: (u64, Vec<usize>),
+}
+
+impl DeletionSearch<'_> {
+    fn search(&mut self, idx: usize, remaining: Option<usize>, freed: u64) {
+        // Nothing can beat freeing exactly what's needed.
+        if freed >= self.best.0 || self.best.0 == self.needed {
+            return;
+        }
+        if freed >= self.needed {
+            self.best = (freed, self.chosen.clone());
+            return;
+        }
+        let candidates = self.candidates;
+        let most = &self.most[remaining.unwrap_or(0)];
+        if idx == candidates.dirs.len() || freed + most[idx] < self.needed {
+            return;
+        }
+        if candidates.deletable[idx] {
+            self.chosen.push(idx);
+            self.search(
+                candidates.ends[idx],
+                remaining.map(|remaining| remaining - 1),
+                freed + candidates.size(idx),
+            );
+            self.chosen.pop();
+        }
+        self.search(idx + 1, remaining, freed);
+    }
+}
+
+// Whether path is dir or somewhere inside it.
+fn is_within(path: &str, dir: &str) -> bool {
+    path == dir
+        || path
+            .strip_prefix(dir)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_tree() -> Node {
+        interpret_session(
+            "$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn check_day07_part1_case1() {
+        assert_eq!(
+            day07(
+                "$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k"
+            )
+            .0,
+            "95437".to_string()
+        )
+    }
+
+    #[test]
+    fn check_day07_part2_case1() {
+        assert_eq!(
+            day07(
+                "$ cd /
+        $ ls
+        dir a
+        14848514 b.txt
+        8504156 c.dat
+        dir d
+        $ cd a
+        $ ls
+        dir e
+        29116 f
+        2557 g
+        62596 h.lst
+        $ cd e
+        $ ls
+        584 i
+        $ cd ..
+        $ cd ..
+        $ cd d
+        $ ls
+        4060174 j
+        8033020 d.log
+        5626152 d.ext
+        7214296 k"
+            )
+            .1,
+            "24933642".to_string()
+        )
+    }
+
+    #[test]
+    fn check_day07_both_case1() {
+        assert_eq!(
+            day07(
+                "$ cd /
+        $ ls
+        dir a
+        14848514 b.txt
+        8504156 c.dat
+        dir d
+        $ cd a
+        $ ls
+        dir e
+        29116 f
+        2557 g
+        62596 h.lst
+        $ cd e
+        $ ls
+        584 i
+        $ cd ..
+        $ cd ..
+        $ cd d
+        $ ls
+        4060174 j
+        8033020 d.log
+        5626152 d.ext
+        7214296 k"
+            ),
+            ("95437".to_string(), "24933642".to_string())
+        )
+    }
+
+    #[test]
+    fn check_day07_small_disk() {
+        // With plenty of free space already, the smallest directory (here b) will do.
+        assert_eq!(
+            day07("$ cd /\n$ ls\n100 a\ndir b\n$ cd b\n$ ls\n50 c"),
+            ("200".to_string(), "50".to_string())
+        );
+        // With more than the whole disk used, enough must go to leave 30000000 free.
+        assert_eq!(
+            day07("$ cd /\n$ ls\n60000000 a\ndir b\n$ cd b\n$ ls\n20000000 c").1,
+            "80000000"
+        );
+    }
+
+    #[test]
+    fn check_day07_interpret_session() {
+        // Revisits a, lists it twice and jumps back to the root part way through.
+        let root = interpret_session(
+            "$ cd a
+$ ls
+10 x
+dir b
+$ cd /
+$ ls
+dir a
+5 y
+$ cd a/b
+$ ls
+20 z
+$ cd ../..
+$ cd a
+$ ls
+10 x
+dir b
+$ cd b/../.
+",
+        )
+        .unwrap();
+        assert_eq!(root.deep_size, 35);
+        assert_eq!(root.children.len(), 1);
+        assert_eq!(root.children[0].deep_size, 30);
+        assert_eq!(root.children[0].children[0].deep_size, 20);
+    }
+
+    #[test]
+    fn check_day07_session_errors() {
+        for (transcript, error) in [
+            ("$ cd /\n$ rm -rf a", "Line 2: unknown command rm"),
+            ("$ cd ..", "Line 1: can't cd above the root directory"),
+            ("$ cd /\n12 a", "Line 2: output 12 a doesn't follow an ls"),
+            (
+                "$ ls\nbig a",
+                "Line 2: expected a file or directory, not big a",
+            ),
+            ("$ cd", "Line 1: couldn't understand command $ cd"),
+        ] {
+            assert_eq!(
+                interpret_session(transcript).err().unwrap().to_string(),
+                error
+            );
+        }
+    }
+
+    #[test]
+    fn check_day07_queries() {
+        let root = example_tree();
+        assert!(
+            matches!(root.lookup("/a/e/"), Some(Entry::Directory(dir)) if dir.deep_size == 584)
+        );
+        assert!(matches!(root.lookup("/d/d.log"), Some(Entry::File(file)) if file.size == 8033020));
+        assert!(root.lookup("/a/x").is_none());
+        assert!(root.lookup("/a/e/i/j").is_none());
+        assert!(root.lookup("a").is_none());
+
+        let paths = |dirs: Vec<&Node>| {
+            dirs.iter()
+                .map(|dir| dir.path.clone())
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(paths(root.largest_directories(2)), vec!["/", "/d"]);
+        let files = |files: Vec<&File>| {
+            files
+                .iter()
+                .map(|file| file.path.clone())
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(files(root.largest_files(2)), vec!["/b.txt", "/c.dat"]);
+        assert_eq!(
+            files(root.matching_files("d.*")),
+            vec!["/d/d.ext", "/d/d.log"]
+        );
+        assert_eq!(
+            files(root.matching_files("?")),
+            vec!["/a/e/i", "/a/f", "/a/g", "/d/j", "/d/k"]
+        );
+        assert_eq!(
+            files(root.matching_files("/a/*")),
+            vec!["/a/f", "/a/g", "/a/h.lst"]
+        );
+        assert_eq!(
+            files(root.matching_files("/a/**")),
+            vec!["/a/e/i", "/a/f", "/a/g", "/a/h.lst"]
+        );
+    }
+
+    #[test]
+    fn check_day07_reports() {
+        let root = example_tree();
+        assert_eq!(
+            root.du_report(Some(1)),
+            "94853\t/a\n24933642\t/d\n48381165\t/\n"
+        );
+        assert_eq!(
+            root.tree_report(),
+            "/ (48381165)
+├── a (94853)
+│   ├── e (584)
+│   │   └── i (584)
+│   ├── f (29116)
+│   ├── g (2557)
+│   └── h.lst (62596)
+├── b.txt (14848514)
+├── c.dat (8504156)
+└── d (24933642)
+    ├── d.ext (5626152)
+    ├── d.log (8033020)
+    ├── j (4060174)
+    └── k (7214296)
+"
+        );
+        let json: serde_json::Value = serde_json::from_str(&root.to_json()).unwrap();
+        assert_eq!(
+            json["children"][0]["children"][0]["files"][0]["path"],
+            "/a/e/i"
+        );
+        assert_eq!(json["deep_size"], 48381165);
+    }
+
+    #[test]
+    fn check_day07_plan_cleanup() {
+        let root = example_tree();
+        let paths = |plan: CleanupPlan| {
+            plan.deletions
+                .iter()
+                .map(|dir| dir.path.clone())
+                .collect::<Vec<String>>()
+        };
+        // Part 2's single directory is also the fewest deletions, but /a and /a/e can't be deleted together.
+        let plan = root
+            .plan_cleanup(70000000, 30000000, &[], PlanGoal::FewestDeletions)
+            .unwrap();
+        assert_eq!((plan.needed, plan.freed()), (8381165, 24933642));
+        assert_eq!(paths(plan), vec!["/d"]);
+        let plan = root
+            .plan_cleanup(48381165, 94000, &[], PlanGoal::LeastData)
+            .unwrap();
+        assert_eq!(paths(plan), vec!["/a"]);
+
+        // Deleting less data can take more directories.
+        let root = interpret_session(
+            "$ ls
+dir a
+dir b
+dir c
+$ cd a
+$ ls
+50 x
+$ cd ../b
+$ ls
+40 y
+dir d
+$ cd d
+$ ls
+15 z
+$ cd /c
+$ ls
+60 w",
+        )
+        .unwrap();
+        let plan = root
+            .plan_cleanup(165, 100, &[], PlanGoal::LeastData)
+            .unwrap();
+        assert_eq!(plan.needed, 100);
+        assert_eq!(paths(plan), vec!["/a", "/b"]);
+        let plan = root
+            .plan_cleanup(165, 100, &["/b/d"], PlanGoal::LeastData)
+            .unwrap();
+        assert_eq!(paths(plan), vec!["/a", "/c"]);
+        let plan = root
+            .plan_cleanup(165, 70, &[], PlanGoal::LeastData)
+            .unwrap();
+        assert_eq!(paths(plan), vec!["/b/d", "/c"]);
+        let plan = root
+            .plan_cleanup(165, 60, &[], PlanGoal::FewestDeletions)
+            .unwrap();
+        assert_eq!(paths(plan), vec!["/c"]);
+        let explanation = root
+            .plan_cleanup(165, 100, &["/b/d"], PlanGoal::LeastData)
+            .unwrap()
+            .explain();
+        assert!(explanation.contains("Nothing in or containing /b/d can be deleted."));
+        assert!(explanation.contains("deleting 2 directories frees 110 bytes, leaving 110 free"));
+
+        assert!(root
+            .plan_cleanup(1000, 10, &[], PlanGoal::LeastData)
+            .unwrap()
+            .deletions
+            .is_empty());
+        assert_eq!(
+            root.plan_cleanup(165, 100, &["/a", "/c"], PlanGoal::LeastData)
+                .err()
+                .unwrap()
+                .to_string(),
+            "need to free 100 bytes but only 55 can be deleted"
+        );
+        // The search doesn't depend on how big the sizes are, so terabytes are as quick to plan for as bytes.
+        let huge = interpret_session(
+            "$ ls\ndir a\ndir b\n$ cd a\n$ ls\n3000000000000 x\n$ cd ..\n$ cd b\n$ ls\n2000000000000 y",
+        )
+        .unwrap();
+        let plan = huge
+            .plan_cleanup(5000000000000, 1500000000000, &[], PlanGoal::LeastData)
+            .unwrap();
+        assert_eq!(paths(plan), vec!["/b"]);
+        assert_eq!(
+            root.plan_cleanup(165, 100, &["/e"], PlanGoal::LeastData)
+                .err()
+                .unwrap()
+                .to_string(),
+            "protected path /e doesn't exist"
+        );
+    }
+}
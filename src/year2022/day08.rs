@@ -0,0 +1,636 @@
+use std::collections::HashSet;
+use std::fmt;
+
+pub fn day08(input_lines: &str) -> (String, String) {
+    let woodland = Woodland::from_str(input_lines).unwrap_or_else(|error| panic!("{}", error));
+    let sightlines = Sightlines::puzzle();
+    let answer1 = woodland.visible_from_edge(&sightlines).len();
+    let answer2 = woodland
+        .positions()
+        .map(|tree| woodland.score(tree, &sightlines).score)
+        .max()
+        .unwrap();
+    (format!("{}", answer1), format!("{}", answer2))
+}
+
+// `cargo run -- report 8 [heatmap|visible|top] [options]` draws the woodland. `heatmap` (the default) shades each tree
+// by its scenic score, `visible` shows which trees can be seen from outside the woodland (or from the observer at x,y
+// with `from=x,y`) and `top [n]` maps the n best treehouse sites (5 by default), optionally only considering trees at
+// least `min-height=h` tall and `edge=d` trees in from the edge. `diagonal` looks in all 8 directions rather than 4,
+// and `taller` lets trees see over trees of the same height.
+pub fn day08_report(input_lines: &str, args: &[String]) -> String {
+    let woodland = Woodland::from_str(input_lines).unwrap_or_else(|error| panic!("{}", error));
+    let mut sightlines = Sightlines::puzzle();
+    let mut observer = None;
+    let mut filter = SiteFilter::default();
+    let mut count = 5;
+    let mut mode = "heatmap";
+    for arg in args {
+        let parse = |value: &str| {
+            value
+                .parse::<usize>()
+                .unwrap_or_else(|_| panic!("Expected a number in {}", arg))
+        };
+        match arg.split_once('=') {
+            Some(("from", position)) => {
+                let (x, y) = position
+                    .split_once(',')
+                    .and_then(|(x, y)| Some((x.parse::<isize>().ok()?, y.parse::<isize>().ok()?)))
+                    .unwrap_or_else(|| panic!("Expected a position like from=3,4, not {}", arg));
+                observer = Some((x, y));
+            }
+            Some(("min-height", height)) => match height.parse::<u8>() {
+                Ok(height) => filter.min_height = height,
+                Err(_) => return format!("Expected a tree height from 0 to 255, not {}\n", height),
+            },
+            Some(("edge", distance)) => filter.min_edge_distance = parse(distance),
+            _ => match arg.as_str() {
+                "heatmap" | "visible" | "top" => mode = arg,
+                "diagonal" => sightlines.directions = &Direction::ALL,
+                "taller" => sightlines.blocks = taller,
+                _ if arg.parse::<usize>().is_ok() => count = parse(arg),
+                _ => panic!("Unknown option {}", arg),
+            },
+        }
+    }
+    match mode {
+        "heatmap" => {
+            let scores = woodland.map(|position| woodland.score(position, &sightlines).score);
+            format!("Scenic scores:\n{}", heatmap(&scores))
+        }
+        "top" => woodland.annotated_map(&woodland.rank_sites(&sightlines, &filter, count)),
+        _ => {
+            let visible = match observer {
+                Some(observer) => woodland.visible_from(observer, &sightlines),
+                None => woodland.visible_from_edge(&sightlines),
+            };
+            let picture = woodland.map(|position| match visible.contains(&position) {
+                true => char::from(b'0' + woodland.height_at(position)),
+                false => '.',
+            });
+            format!(
+                "{} trees visible:\n{}",
+                visible.len(),
+                picture
+                    .iter()
+                    .map(|row| row.iter().collect::<String>() + "\n")
+                    .collect::<String>()
+            )
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum WoodlandError {
+    InvalidHeight {
+        row: usize,
+        column: usize,
+        cell: char,
+    },
+    RaggedRow(usize),
+}
+
+impl fmt::Display for WoodlandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WoodlandError::InvalidHeight { row, column, cell } => write!(
+                f,
+                "Row {}, column {}: expected a tree height, not {}",
+                row, column, cell
+            ),
+            WoodlandError::RaggedRow(row) => {
+                write!(f, "Row {} isn't the same length as the first row", row)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Left,
+        Direction::Down,
+        Direction::Right,
+    ];
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::Left,
+        Direction::Down,
+        Direction::Right,
+        Direction::UpLeft,
+        Direction::UpRight,
+        Direction::DownLeft,
+        Direction::DownRight,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Direction::Up => "up",
+            Direction::Down => "down",
+            Direction::Left => "left",
+            Direction::Right => "right",
+            Direction::UpLeft => "up-left",
+            Direction::UpRight => "up-right",
+            Direction::DownLeft => "down-left",
+            Direction::DownRight => "down-right",
+        }
+    }
+
+    // The character for a line of sight going this way.
+    fn line_symbol(&self) -> char {
+        match self {
+            Direction::Up | Direction::Down => '|',
+            Direction::Left | Direction::Right => '-',
+            Direction::UpLeft | Direction::DownRight => '\\',
+            Direction::UpRight | Direction::DownLeft => '/',
+        }
+    }
+
+    pub fn offset(&self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+            Direction::UpLeft => (-1, -1),
+            Direction::UpRight => (1, -1),
+            Direction::DownLeft => (-1, 1),
+            Direction::DownRight => (1, 1),
+        }
+    }
+}
+
+// Which directions to look in, and whether a tree of the first height has its view blocked by a tree of the second.
+pub struct Sightlines {
+    pub directions: &'static [Direction],
+    pub blocks: fn(u8, u8) -> bool,
+}
+
+impl Sightlines {
+    pub fn puzzle() -> Self {
+        Sightlines {
+            directions: &Direction::ORTHOGONAL,
+            blocks: at_least_as_tall,
+        }
+    }
+}
+
+pub fn at_least_as_tall(viewer: u8, other: u8) -> bool {
+    other >= viewer
+}
+
+pub fn taller(viewer: u8, other: u8) -> bool {
+    other > viewer
+}
+
+// How far a tree can see in one direction, and the tree that stops it if it doesn't see all the way to the edge.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct View {
+    pub direction: Direction,
+    pub distance: usize,
+    pub blocker: Option<(usize, usize)>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ScoreBreakdown {
+    pub views: Vec<View>,
+    pub score: u64,
+}
+
+// Which trees are worth considering for a treehouse.
+#[derive(Default)]
+pub struct SiteFilter {
+    pub min_height: u8,
+    // How many trees in from the edge the site must be, where 0 allows the edge itself.
+    pub min_edge_distance: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Site {
+    pub position: (usize, usize),
+    pub height: u8,
+    pub breakdown: ScoreBreakdown,
+}
+
+// Tree heights by row then column, with positions given as (x, y) = (column, row).
+pub struct Woodland {
+    heights: Vec<Vec<u8>>,
+    width: usize,
+}
+
+impl Woodland {
+    pub fn from_str(input_lines: &str) -> Result<Self, WoodlandError> {
+        let heights = input_lines
+            .lines()
+            .enumerate()
+            .map(|(row, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(column, cell)| {
+                        cell.to_digit(10)
+                            .map(|height| height as u8)
+                            .ok_or(WoodlandError::InvalidHeight { row, column, cell })
+                    })
+                    .collect::<Result<Vec<u8>, WoodlandError>>()
+            })
+            .collect::<Result<Vec<Vec<u8>>, WoodlandError>>()?;
+        let width = heights.first().map_or(0, Vec::len);
+        if let Some(row) = heights.iter().position(|row| row.len() != width) {
+            return Err(WoodlandError::RaggedRow(row));
+        }
+        Ok(Woodland { heights, width })
+    }
+
+    pub fn height_at(&self, (x, y): (usize, usize)) -> u8 {
+        self.heights[y][x]
+    }
+
+    // The position a step away in the given direction, which may be outside the woodland.
+    fn step(&self, (x, y): (isize, isize), direction: Direction) -> (isize, isize) {
+        let (dx, dy) = direction.offset();
+        (x + dx, y + dy)
+    }
+
+    fn in_bounds(&self, (x, y): (isize, isize)) -> Option<(usize, usize)> {
+        (x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.heights.len())
+            .then_some((x as usize, y as usize))
+    }
+
+    // The trees in a straight line from (but not including) a position, nearest first.
+    fn line_from(
+        &self,
+        start: (isize, isize),
+        direction: Direction,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let mut position = start;
+        std::iter::from_fn(move || {
+            position = self.step(position, direction);
+            self.in_bounds(position)
+        })
+    }
+
+    pub fn view(
+        &self,
+        tree: (usize, usize),
+        direction: Direction,
+        sightlines: &Sightlines,
+    ) -> View {
+        let height = self.height_at(tree);
+        let mut distance = 0;
+        for other in self.line_from((tree.0 as isize, tree.1 as isize), direction) {
+            distance += 1;
+            if (sightlines.blocks)(height, self.height_at(other)) {
+                return View {
+                    direction,
+                    distance,
+                    blocker: Some(other),
+                };
+            }
+        }
+        View {
+            direction,
+            distance,
+            blocker: None,
+        }
+    }
+
+    // A tree's view in each direction, and its scenic score: the product of the view distances.
+    pub fn score(&self, tree: (usize, usize), sightlines: &Sightlines) -> ScoreBreakdown {
+        let views = sightlines
+            .directions
+            .iter()
+            .map(|direction| self.view(tree, *direction, sightlines))
+            .collect::<Vec<View>>();
+        let score = views.iter().map(|view| view.distance as u64).product();
+        ScoreBreakdown { views, score }
+    }
+
+    // Trees that can see out of the woodland in at least one direction, so can be seen from outside it.
+    pub fn visible_from_edge(&self, sightlines: &Sightlines) -> HashSet<(usize, usize)> {
+        self.positions()
+            .filter(|tree| {
+                sightlines
+                    .directions
+                    .iter()
+                    .any(|direction| self.view(*tree, *direction, sightlines).blocker.is_none())
+            })
+            .collect()
+    }
+
+    // Trees an observer at ground level can see looking out along each direction, which may be from inside the
+    // woodland or outside it. The observer sees a tree if none of the trees in between block the view of it.
+    pub fn visible_from(
+        &self,
+        observer: (isize, isize),
+        sightlines: &Sightlines,
+    ) -> HashSet<(usize, usize)> {
+        let mut visible = HashSet::new();
+        for direction in sightlines.directions {
+            let mut tallest: Option<u8> = None;
+            for tree in self.line_from(observer, *direction) {
+                let height = self.height_at(tree);
+                if tallest.is_none_or(|tallest| !(sightlines.blocks)(height, tallest)) {
+                    visible.insert(tree);
+                }
+                tallest = tallest.max(Some(height));
+            }
+        }
+        visible
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.heights.len()).flat_map(move |y| (0..self.width).map(move |x| (x, y)))
+    }
+
+    // How many trees in from the nearest edge a tree is.
+    pub fn edge_distance(&self, (x, y): (usize, usize)) -> usize {
+        [x, y, self.width - 1 - x, self.heights.len() - 1 - y]
+            .into_iter()
+            .min()
+            .unwrap()
+    }
+
+    // The n trees passing the filter with the best scenic scores, best first. Ties go to the tree nearest the top, then
+    // the left.
+    pub fn rank_sites(&self, sightlines: &Sightlines, filter: &SiteFilter, n: usize) -> Vec<Site> {
+        let mut sites = self
+            .positions()
+            .filter(|tree| {
+                self.height_at(*tree) >= filter.min_height
+                    && self.edge_distance(*tree) >= filter.min_edge_distance
+            })
+            .map(|tree| Site {
+                position: tree,
+                height: self.height_at(tree),
+                breakdown: self.score(tree, sightlines),
+            })
+            .collect::<Vec<Site>>();
+        sites.sort_by(|a, b| {
+            b.breakdown
+                .score
+                .cmp(&a.breakdown.score)
+                .then((a.position.1, a.position.0).cmp(&(b.position.1, b.position.0)))
+        });
+        sites.truncate(n);
+        sites
+    }
+
+    // A map of the sites, labelled A, B, C... in rank order, with their lines of sight drawn out to the trees blocking
+    // them (marked #). Other trees are left as dots. A key below the map gives each site's view in each direction.
+    pub fn annotated_map(&self, sites: &[Site]) -> String {
+        let mut picture = self.map(|_| '.');
+        let label = |rank: usize| char::from(b'A' + rank as u8);
+        for site in sites.iter().rev() {
+            let (x, y) = (site.position.0 as isize, site.position.1 as isize);
+            for view in &site.breakdown.views {
+                for (x, y) in self.line_from((x, y), view.direction).take(view.distance) {
+                    let cell = &mut picture[y][x];
+                    if Some((x, y)) == view.blocker {
+                        *cell = '#';
+                    } else if *cell == '.' {
+                        *cell = view.direction.line_symbol();
+                    }
+                }
+            }
+        }
+        for (rank, site) in sites.iter().enumerate().take(26) {
+            picture[site.position.1][site.position.0] = label(rank);
+        }
+        let mut report = picture
+            .iter()
+            .map(|row| row.iter().collect::<String>() + "\n")
+            .collect::<String>();
+        for (rank, site) in sites.iter().enumerate() {
+            let label = if rank < 26 { label(rank) } else { ' ' };
+            report.push_str(&format!(
+                "{} ({}, {}) height {}, score {}:",
+                label, site.position.0, site.position.1, site.height, site.breakdown.score
+            ));
+            for view in &site.breakdown.views {
+                report.push_str(&format!(" {} {}", view.direction.name(), view.distance));
+                match view.blocker {
+                    Some((x, y)) => report.push_str(&format!(
+                        " (blocked by {} at {}, {})",
+                        self.height_at((x, y)),
+                        x,
+                        y
+                    )),
+                    None => report.push_str(" (to the edge)"),
+                }
+            }
+            report.push('\n');
+        }
+        report
+    }
+
+    // A grid of some value for every tree.
+    pub fn map<T>(&self, value: impl Fn((usize, usize)) -> T) -> Vec<Vec<T>> {
+        (0..self.heights.len())
+            .map(|y| (0..self.width).map(|x| value((x, y))).collect())
+            .collect()
+    }
+}
+
+const HEATMAP_SHADES: &[char] = &[' ', '.', ':', '-', '=', '+', '*', '#', '%', '@'];
+
+// Shade each value by its rank, so each shade but the first (for zero) covers about the same number of trees however
+// skewed the values are, with a legend of the largest value in each shade.
+pub fn heatmap(values: &[Vec<u64>]) -> String {
+    let mut non_zero = values
+        .iter()
+        .flatten()
+        .copied()
+        .filter(|value| *value > 0)
+        .collect::<Vec<u64>>();
+    non_zero.sort_unstable();
+    let shades = HEATMAP_SHADES.len() - 1;
+    let upper_bounds = (1..=shades)
+        .filter_map(|shade| non_zero.get((non_zero.len() * shade).div_ceil(shades).checked_sub(1)?))
+        .copied()
+        .collect::<Vec<u64>>();
+    let shade = |value: u64| match value {
+        0 => HEATMAP_SHADES[0],
+        _ => HEATMAP_SHADES[1 + upper_bounds.partition_point(|bound| *bound < value)],
+    };
+    let mut picture = values
+        .iter()
+        .map(|row| row.iter().map(|value| shade(*value)).collect::<String>() + "\n")
+        .collect::<String>();
+    picture.push_str(&format!("'{}' is 0", HEATMAP_SHADES[0]));
+    let mut previous = 0;
+    for (idx, bound) in upper_bounds.iter().enumerate() {
+        if *bound > previous {
+            picture.push_str(&format!(", '{}' up to {}", HEATMAP_SHADES[idx + 1], bound));
+            previous = *bound;
+        }
+    }
+    picture.push('\n');
+    picture
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_day08_part1_case1() {
+        assert_eq!(
+            day08(
+                "30373
+25512
+65332
+33549
+35390"
+            )
+            .0,
+            "21".to_string()
+        )
+    }
+
+    #[test]
+    fn check_day08_part2_case1() {
+        assert_eq!(
+            day08(
+                "30373
+25512
+65332
+33549
+35390"
+            )
+            .1,
+            "8".to_string()
+        )
+    }
+
+    #[test]
+    fn check_day08_both_case1() {
+        assert_eq!(
+            day08(
+                "30373
+25512
+65332
+33549
+35390"
+            ),
+            ("21".to_string(), "8".to_string())
+        )
+    }
+
+    #[test]
+    fn check_day08_woodland() {
+        let woodland = Woodland::from_str("30373\n25512\n65332\n33549\n35390").unwrap();
+        let sightlines = Sightlines::puzzle();
+        assert_eq!(woodland.visible_from_edge(&sightlines).len(), 21);
+        assert_eq!(woodland.score((2, 1), &sightlines).score, 4);
+
+        let views = woodland.score((2, 3), &sightlines).views;
+        assert_eq!(
+            views
+                .iter()
+                .map(|view| (view.distance, view.blocker))
+                .collect::<Vec<(usize, Option<(usize, usize)>)>>(),
+            vec![(2, Some((2, 1))), (2, None), (1, None), (2, Some((4, 3)))]
+        );
+        let diagonal = Sightlines {
+            directions: &Direction::ALL,
+            blocks: at_least_as_tall,
+        };
+        assert_eq!(woodland.score((2, 3), &diagonal).score, 8 * 2);
+        assert_eq!(woodland.visible_from_edge(&diagonal).len(), 22);
+
+        let from_left = woodland.visible_from((-1, 0), &sightlines);
+        assert_eq!(from_left, HashSet::from([(0, 0), (3, 0)]));
+        let over_equals = Sightlines {
+            directions: &Direction::ORTHOGONAL,
+            blocks: taller,
+        };
+        let from_left = woodland.visible_from((-1, 0), &over_equals);
+        assert_eq!(from_left, HashSet::from([(0, 0), (2, 0), (3, 0)]));
+
+        assert!(matches!(
+            Woodland::from_str("123\n45"),
+            Err(WoodlandError::RaggedRow(1))
+        ));
+        assert!(matches!(
+            Woodland::from_str("12a"),
+            Err(WoodlandError::InvalidHeight { column: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn check_day08_heatmap() {
+        assert_eq!(
+            heatmap(&[vec![0, 1], vec![2, 3]]),
+            " .\n=#\n' ' is 0, '.' up to 1, '=' up to 2, '#' up to 3\n"
+        );
+    }
+
+    #[test]
+    fn check_day08_rank_sites() {
+        let woodland = Woodland::from_str("30373\n25512\n65332\n33549\n35390").unwrap();
+        let sightlines = Sightlines::puzzle();
+        let filter = SiteFilter {
+            min_height: 5,
+            min_edge_distance: 1,
+        };
+        let sites = woodland.rank_sites(&sightlines, &filter, 2);
+        assert_eq!(
+            sites
+                .iter()
+                .map(|site| (site.position, site.breakdown.score))
+                .collect::<Vec<((usize, usize), u64)>>(),
+            vec![((2, 3), 8), ((1, 2), 6)]
+        );
+        assert_eq!(
+            woodland.annotated_map(&sites),
+            ".....
+.##..
+#B---
+-|A-#
+.#|..
+A (2, 3) height 5, score 8: up 2 (blocked by 5 at 2, 1) left 2 (to the edge) down 1 (to the edge) right 2 (blocked by 9 at 4, 3)
+B (1, 2) height 5, score 6: up 1 (blocked by 5 at 1, 1) left 1 (blocked by 6 at 0, 2) down 2 (blocked by 5 at 1, 4) right 3 (to the edge)
+"
+        );
+
+        let filter = SiteFilter {
+            min_height: 6,
+            min_edge_distance: 1,
+        };
+        assert!(woodland.rank_sites(&sightlines, &filter, 5).is_empty());
+        assert_eq!(woodland.edge_distance((1, 3)), 1);
+    }
+
+    #[test]
+    fn check_day08_report_options() {
+        let trees = "30373\n25512\n65332\n33549\n35390";
+        let option = |options: &[&str]| {
+            day08_report(
+                trees,
+                &options
+                    .iter()
+                    .map(|option| option.to_string())
+                    .collect::<Vec<String>>(),
+            )
+        };
+        assert!(
+            option(&["top", "2", "min-height=5", "edge=1"]).contains("A (2, 3) height 5, score 8")
+        );
+        assert_eq!(
+            option(&["top", "min-height=300"]),
+            "Expected a tree height from 0 to 255, not 300\n"
+        );
+    }
+}
@@ -0,0 +1,371 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use regex::Regex;
+
+pub fn day09(input_lines: &str) -> (String, String) {
+    let motions = parse_motions(input_lines).unwrap_or_else(|error| panic!("{}", error));
+    let mut pt1_rope = Rope::new(2, 1);
+    let mut pt2_rope = Rope::new(10, 1);
+    for motion in &motions {
+        pt1_rope.apply(motion);
+        pt2_rope.apply(motion);
+    }
+    let answer1 = pt1_rope.visited(1).len();
+    let answer2 = pt2_rope.visited(9).len();
+    (format!("{}", answer1), format!("{}", answer2))
+}
+
+// `cargo run -- report 9 [knots=n] [slack=s] [trail=k]` pulls a rope of n knots (10 by default) through the motions,
+// letting each knot stray s steps from the one ahead (1 by default). It counts where every knot has been and draws the
+// trail of knot k (the tail by default).
+pub fn day09_report(input_lines: &str, args: &[String]) -> String {
+    let motions = parse_motions(input_lines).unwrap_or_else(|error| panic!("{}", error));
+    let (mut knot_count, mut slack, mut trail) = (10, 1, None);
+    for arg in args {
+        let (name, value) = arg
+            .split_once('=')
+            .unwrap_or_else(|| panic!("Expected an option like knots=10, not {}", arg));
+        let value = value
+            .parse::<usize>()
+            .unwrap_or_else(|_| panic!("Expected a number in {}", arg));
+        match name {
+            "knots" => knot_count = value,
+            "slack" => slack = value as i32,
+            "trail" => trail = Some(value),
+            _ => panic!("Unknown option {}", arg),
+        }
+    }
+    if knot_count == 0 {
+        return "A rope needs at least one knot\n".to_string();
+    }
+    let trail = trail.unwrap_or(knot_count - 1);
+    if trail >= knot_count {
+        return format!(
+            "There's no knot {} to trail on a rope of {} knots\n",
+            trail, knot_count
+        );
+    }
+    let mut rope = Rope::new(knot_count, slack);
+    for motion in &motions {
+        rope.apply(motion);
+    }
+    let mut report = String::new();
+    for (knot, position) in rope.knots().iter().enumerate() {
+        report.push_str(&format!(
+            "{} visited {} positions, ending at {:?}\n",
+            knot_label(knot),
+            rope.visited(knot).len(),
+            position
+        ));
+    }
+    report + &rope.render_trail(trail) + "\n"
+}
+
+#[derive(Debug)]
+pub enum RopeError {
+    InvalidMotion { line: usize, text: String },
+}
+
+impl fmt::Display for RopeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RopeError::InvalidMotion { line, text } => {
+                write!(f, "Line {}: expected a motion, not {}", line, text)
+            }
+        }
+    }
+}
+
+// The head moving by delta, repeat times over. Steps in a direction (R, L, U, D and the diagonals UR, UL, DR and DL)
+// are single square moves, while jumps (`J <dx> <dy>`) move the head all at once.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Motion {
+    pub delta: (i32, i32),
+    pub repeat: u32,
+}
+
+pub fn parse_motions(input_lines: &str) -> Result<Vec<Motion>, RopeError> {
+    let step_regex = Regex::new(r"^([RLUD]|UR|UL|DR|DL) (\d+)$").unwrap();
+    let jump_regex = Regex::new(r"^J (-?\d+) (-?\d+)$").unwrap();
+    input_lines
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(line_idx, line)| {
+            let invalid = || RopeError::InvalidMotion {
+                line: line_idx + 1,
+                text: line.to_string(),
+            };
+            let line = line.trim();
+            if let Some(jump) = jump_regex.captures(line) {
+                let dx = jump[1].parse::<i32>().map_err(|_| invalid())?;
+                let dy = jump[2].parse::<i32>().map_err(|_| invalid())?;
+                return Ok(Motion {
+                    delta: (dx, dy),
+                    repeat: 1,
+                });
+            }
+            let step = step_regex.captures(line).ok_or_else(invalid)?;
+            let delta = step[1]
+                .chars()
+                .fold((0, 0), |(x, y), direction| match direction {
+                    'R' => (x + 1, y),
+                    'L' => (x - 1, y),
+                    'U' => (x, y + 1),
+                    _ => (x, y - 1),
+                });
+            let repeat = step[2].parse::<u32>().map_err(|_| invalid())?;
+            Ok(Motion { delta, repeat })
+        })
+        .collect()
+}
+
+// A rope of knots, the first being the head. Each knot follows the one ahead of it whenever that one gets more than
+// slack squares away (counting diagonals as one), moving a square at a time, diagonally if it's not in line.
+pub struct Rope {
+    knots: Vec<(i32, i32)>,
+    slack: i32,
+    visited: Vec<HashSet<(i32, i32)>>,
+}
+
+impl Rope {
+    pub fn new(knot_count: usize, slack: i32) -> Self {
+        assert!(knot_count > 0, "A rope needs at least one knot");
+        Rope {
+            knots: vec![(0, 0); knot_count],
+            slack,
+            visited: vec![HashSet::from([(0, 0)]); knot_count],
+        }
+    }
+
+    pub fn knots(&self) -> &[(i32, i32)] {
+        &self.knots
+    }
+
+    pub fn visited(&self, knot: usize) -> &HashSet<(i32, i32)> {
+        &self.visited[knot]
+    }
+
+    pub fn apply(&mut self, motion: &Motion) {
+        for _ in 0..motion.repeat {
+            self.move_head(motion.delta);
+        }
+    }
+
+    pub fn move_head(&mut self, (dx, dy): (i32, i32)) {
+        self.knots[0] = (self.knots[0].0 + dx, self.knots[0].1 + dy);
+        self.visited[0].insert(self.knots[0]);
+        // After a big jump the knots take several rounds of single steps to catch up, each following where the knot
+        // ahead has got to so far. After a single step one round is always enough.
+        while self.follow_once() {}
+    }
+
+    // Move every knot that's too far behind the one ahead a step closer. Returns whether any moved.
+    fn follow_once(&mut self) -> bool {
+        let mut moved = false;
+        for knot in 1..self.knots.len() {
+            let (ahead, behind) = (self.knots[knot - 1], self.knots[knot]);
+            let (x, y) = (ahead.0 - behind.0, ahead.1 - behind.1);
+            if x.abs().max(y.abs()) > self.slack {
+                self.knots[knot] = (behind.0 + x.signum(), behind.1 + y.signum());
+                self.visited[knot].insert(self.knots[knot]);
+                moved = true;
+            }
+        }
+        moved
+    }
+
+    // Draw everywhere the given knot has been (#) in the style of the puzzle, with the start (s) and the knots' current
+    // positions on top. Up is up.
+    pub fn render_trail(&self, knot: usize) -> String {
+        let points = self.visited.iter().flatten().chain(&self.knots);
+        let (min_x, max_x) = points
+            .clone()
+            .map(|point| point.0)
+            .fold((0, 0), |(min, max), x| (min.min(x), max.max(x)));
+        let (min_y, max_y) = points
+            .map(|point| point.1)
+            .fold((0, 0), |(min, max), y| (min.min(y), max.max(y)));
+        (min_y..=max_y)
+            .rev()
+            .map(|y| {
+                (min_x..=max_x)
+                    .map(|x| {
+                        if let Some(idx) =
+                            self.knots.iter().position(|position| *position == (x, y))
+                        {
+                            knot_label(idx).chars().last().unwrap()
+                        } else if (x, y) == (0, 0) {
+                            's'
+                        } else if self.visited[knot].contains(&(x, y)) {
+                            '#'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+// H for the head, then the knots numbered from 1. Trail drawings only have room for the last digit.
+fn knot_label(knot: usize) -> String {
+    match knot {
+        0 => "H".to_string(),
+        _ => knot.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_day09_part1_case1() {
+        assert_eq!(
+            day09(
+                "R 4
+U 4
+L 3
+D 1
+R 4
+D 1
+L 5
+R 2"
+            )
+            .0,
+            "13".to_string()
+        )
+    }
+
+    #[test]
+    fn check_day09_part2_case1() {
+        assert_eq!(
+            day09(
+                "R 4
+U 4
+L 3
+D 1
+R 4
+D 1
+L 5
+R 2"
+            )
+            .1,
+            "1".to_string()
+        )
+    }
+
+    #[test]
+    fn check_day09_part2_case2() {
+        assert_eq!(
+            day09(
+                "R 5
+U 8
+L 8
+D 3
+R 17
+D 10
+L 25
+U 20"
+            )
+            .1,
+            "36".to_string()
+        )
+    }
+
+    #[test]
+    fn check_day09_both_case1() {
+        assert_eq!(
+            day09(
+                "R 4
+U 4
+L 3
+D 1
+R 4
+D 1
+L 5
+R 2"
+            ),
+            ("13".to_string(), "1".to_string())
+        )
+    }
+
+    #[test]
+    fn check_day09_parse_motions() {
+        assert_eq!(
+            parse_motions("R 4\nUR 2\n\nJ -3 4").unwrap(),
+            vec![
+                Motion {
+                    delta: (1, 0),
+                    repeat: 4
+                },
+                Motion {
+                    delta: (1, 1),
+                    repeat: 2
+                },
+                Motion {
+                    delta: (-3, 4),
+                    repeat: 1
+                },
+            ]
+        );
+        assert_eq!(
+            parse_motions("R 4\nX 1").err().unwrap().to_string(),
+            "Line 2: expected a motion, not X 1"
+        );
+    }
+
+    #[test]
+    fn check_day09_rope() {
+        // Followers catch up after a jump a step at a time.
+        let mut rope = Rope::new(3, 1);
+        rope.move_head((4, 0));
+        assert_eq!(rope.knots(), &[(4, 0), (3, 0), (2, 0)]);
+        assert_eq!(rope.visited(1).len(), 4);
+        assert_eq!(rope.visited(2).len(), 3);
+
+        let mut rope = Rope::new(2, 1);
+        rope.move_head((3, 2));
+        assert_eq!(rope.visited(1), &HashSet::from([(0, 0), (1, 1), (2, 2)]));
+
+        let mut rope = Rope::new(2, 2);
+        rope.apply(&Motion {
+            delta: (1, 0),
+            repeat: 3,
+        });
+        assert_eq!(rope.visited(1), &HashSet::from([(0, 0), (1, 0)]));
+    }
+
+    #[test]
+    fn check_day09_render_trail() {
+        let mut rope = Rope::new(2, 1);
+        for motion in parse_motions("R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2").unwrap() {
+            rope.apply(&motion);
+        }
+        assert_eq!(
+            rope.render_trail(1),
+            "..##..\n...##.\n.1H##.\n....#.\ns###.."
+        );
+    }
+
+    #[test]
+    fn check_day09_report_options() {
+        let motions = "R 4\nU 4";
+        assert!(
+            day09_report(motions, &["knots=3".to_string(), "trail=2".to_string()])
+                .starts_with("H visited 9 positions")
+        );
+        assert_eq!(
+            day09_report(motions, &["knots=0".to_string()]),
+            "A rope needs at least one knot\n"
+        );
+        assert_eq!(
+            day09_report(motions, &["knots=3".to_string(), "trail=3".to_string()]),
+            "There's no knot 3 to trail on a rope of 3 knots\n"
+        );
+    }
+}
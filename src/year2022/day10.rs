@@ -0,0 +1,1224 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::simulation::{Condition, Simulation};
+
+pub fn day10(input_lines: &str) -> (String, String) {
+    let instruction_set = InstructionSet::standard();
+    let program = instruction_set
+        .parse(input_lines)
+        .unwrap_or_else(|error| panic!("{}", error));
+    let mut answer1 = 0;
+    let answer2 = {
+        let mut cpu = Cpu::new(instruction_set, program, Crt::new(40, 6, 3));
+        cpu.set_tracer(|trace| {
+            if trace.cycle % 40 == 20 && trace.cycle <= 220 {
+                answer1 += trace.cycle as i64 * trace.registers.get("X");
+            }
+        });
+        while cpu.step() {}
+        cpu.crt().render()
+    };
+    (format!("{}", answer1), answer2)
+}
+
+// `cargo run -- report 10 [width=w] [height=h] [sprite=s] [break=<condition>...] [trace]` runs the program on a CRT of
+// the given size (40x6 by default) with a sprite s pixels wide (3 by default), stopping at the first breakpoint
+// condition to hold, e.g. `break="X > 30"` or `break="cycle = 100"`. `trace` lists the registers during every cycle.
+// `assemble=<text>` ignores the input and writes a program that draws the text in block letters on that CRT instead.
+pub fn day10_report(input_lines: &str, args: &[String]) -> String {
+    let (mut width, mut height, mut sprite_width) = (40, 6, 3);
+    let mut breakpoints = vec![];
+    let mut tracing = false;
+    let mut text = None;
+    for arg in args {
+        if arg == "trace" {
+            tracing = true;
+            continue;
+        }
+        let (name, value) = arg
+            .split_once('=')
+            .unwrap_or_else(|| panic!("Expected an option like width=40, not {}", arg));
+        let number = || {
+            value
+                .parse::<usize>()
+                .unwrap_or_else(|_| panic!("Expected a number in {}", arg))
+        };
+        match name {
+            "width" => width = number(),
+            "height" => height = number(),
+            "sprite" => sprite_width = number(),
+            "assemble" => text = Some(value),
+            "break" => breakpoints.push(
+                Condition::from_str(value)
+                    .unwrap_or_else(|| panic!("Expected a condition like X > 30, not {}", value)),
+            ),
+            _ => panic!("Unknown option {}", arg),
+        }
+    }
+    if let Some(text) = text {
+        let program = assembler::render_text(text, width, height)
+            .and_then(|image| assembler::assemble(&image, sprite_width))
+            .unwrap_or_else(|error| panic!("{}", error));
+        return program + "\n";
+    }
+    let instruction_set = InstructionSet::standard();
+    let program = instruction_set
+        .parse(input_lines)
+        .unwrap_or_else(|error| panic!("{}", error));
+    let mut trace = String::new();
+    let mut cpu = Cpu::new(
+        instruction_set,
+        program,
+        Crt::new(width, height, sprite_width),
+    );
+    for breakpoint in breakpoints {
+        cpu.add_breakpoint(breakpoint);
+    }
+    if tracing {
+        cpu.set_tracer(|cycle| {
+            let registers = cycle
+                .registers
+                .values
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect::<Vec<String>>()
+                .join(" ");
+            trace.push_str(&format!(
+                "{:>5}  {:<6} (line {})  {}\n",
+                cycle.cycle, cycle.opcode, cycle.line, registers
+            ));
+        });
+    }
+    let mut report = match cpu.run() {
+        Some(breakpoint) => format!("Stopped at breakpoint {}\n", breakpoint + 1),
+        None => "Finished\n".to_string(),
+    };
+    for (name, value) in cpu.inspect() {
+        report.push_str(&format!("{}: {}\n", name, value));
+    }
+    report = report + &cpu.crt().render() + "\n";
+    drop(cpu);
+    trace + &report
+}
+
+// `cargo run -- debug 10` steps through the program a cycle at a time, drawing the screen so far.
+pub fn day10_simulation(input_lines: &str, _part: u8) -> Box<dyn Simulation> {
+    let instruction_set = InstructionSet::standard();
+    let program = instruction_set
+        .parse(input_lines)
+        .unwrap_or_else(|error| panic!("{}", error));
+    Box::new(Cpu::new(instruction_set, program, Crt::new(40, 6, 3)))
+}
+
+#[derive(Debug)]
+pub enum CpuError {
+    UnknownOpcode { line: usize, name: String },
+    WrongOperandCount { line: usize, expected: usize },
+    InvalidOperand { line: usize, operand: String },
+}
+
+impl fmt::Display for CpuError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CpuError::UnknownOpcode { line, name } => {
+                write!(f, "Line {}: unknown instruction {}", line, name)
+            }
+            CpuError::WrongOperandCount { line, expected } => {
+                write!(f, "Line {}: expected {} operand(s)", line, expected)
+            }
+            CpuError::InvalidOperand { line, operand } => write!(
+                f,
+                "Line {}: expected a number or register name, not {}",
+                line, operand
+            ),
+        }
+    }
+}
+
+// Registers are named, and any that haven't been set read as 0 apart from X, which starts at 1.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Registers {
+    values: BTreeMap<String, i64>,
+}
+
+impl Registers {
+    pub fn new() -> Self {
+        Registers {
+            values: BTreeMap::from([("X".to_string(), 1)]),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> i64 {
+        self.values.get(name).copied().unwrap_or(0)
+    }
+
+    pub fn set(&mut self, name: &str, value: i64) {
+        self.values.insert(name.to_string(), value);
+    }
+
+    pub fn value(&self, operand: &Operand) -> i64 {
+        match operand {
+            Operand::Value(value) => *value,
+            Operand::Register(name) => self.get(name),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Operand {
+    Value(i64),
+    Register(String),
+}
+
+// An instruction the CPU understands, taking some number of cycles and then changing the registers at the end of its
+// last cycle.
+#[derive(Clone)]
+pub struct Opcode {
+    pub name: &'static str,
+    pub cycles: u32,
+    pub operands: usize,
+    pub execute: fn(&mut Registers, &[Operand]),
+}
+
+#[derive(Clone)]
+pub struct InstructionSet {
+    opcodes: Vec<Opcode>,
+}
+
+impl InstructionSet {
+    // The puzzle's addx and noop.
+    pub fn standard() -> Self {
+        InstructionSet { opcodes: vec![] }
+            .with(Opcode {
+                name: "addx",
+                cycles: 2,
+                operands: 1,
+                execute: |registers, operands| {
+                    registers.set("X", registers.get("X") + registers.value(&operands[0]))
+                },
+            })
+            .with(Opcode {
+                name: "noop",
+                cycles: 1,
+                operands: 0,
+                execute: |_, _| {},
+            })
+    }
+
+    // Add an opcode, replacing any existing one with the same name.
+    pub fn with(mut self, opcode: Opcode) -> Self {
+        assert!(
+            opcode.cycles > 0,
+            "{} needs to take at least one cycle",
+            opcode.name
+        );
+        self.opcodes.retain(|existing| existing.name != opcode.name);
+        self.opcodes.push(opcode);
+        self
+    }
+
+    pub fn parse(&self, program: &str) -> Result<Vec<Instruction>, CpuError> {
+        program
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(line_idx, line)| {
+                let line_no = line_idx + 1;
+                let mut tokens = line.split_whitespace();
+                let name = tokens.next().unwrap();
+                let opcode = self
+                    .opcodes
+                    .iter()
+                    .position(|opcode| opcode.name == name)
+                    .ok_or_else(|| CpuError::UnknownOpcode {
+                        line: line_no,
+                        name: name.to_string(),
+                    })?;
+                let operands = tokens
+                    .map(|operand| match operand.parse::<i64>() {
+                        Ok(value) => Ok(Operand::Value(value)),
+                        Err(_) if operand.chars().all(|c| c.is_ascii_alphabetic()) => {
+                            Ok(Operand::Register(operand.to_string()))
+                        }
+                        Err(_) => Err(CpuError::InvalidOperand {
+                            line: line_no,
+                            operand: operand.to_string(),
+                        }),
+                    })
+                    .collect::<Result<Vec<Operand>, CpuError>>()?;
+                if operands.len() != self.opcodes[opcode].operands {
+                    return Err(CpuError::WrongOperandCount {
+                        line: line_no,
+                        expected: self.opcodes[opcode].operands,
+                    });
+                }
+                Ok(Instruction {
+                    line: line_no,
+                    opcode,
+                    operands,
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Instruction {
+    pub line: usize,
+    opcode: usize,
+    pub operands: Vec<Operand>,
+}
+
+// A screen drawn a pixel per cycle, left to right and top to bottom, wrapping back to the top if the program runs on.
+// A pixel lights if the sprite, centred on X, covers its column.
+pub struct Crt {
+    width: usize,
+    height: usize,
+    sprite_width: usize,
+    pixels: Vec<bool>,
+}
+
+impl Crt {
+    pub fn new(width: usize, height: usize, sprite_width: usize) -> Self {
+        Crt {
+            width,
+            height,
+            sprite_width,
+            pixels: vec![false; width * height],
+        }
+    }
+
+    // Draw the pixel for the given cycle (counting from 1) with the sprite at x.
+    fn draw(&mut self, cycle: u64, x: i64) {
+        if self.pixels.is_empty() {
+            return;
+        }
+        let idx = (cycle - 1) as usize % self.pixels.len();
+        let column = (idx % self.width) as i64;
+        let left = x - (self.sprite_width as i64 - 1) / 2;
+        self.pixels[idx] = (left..left + self.sprite_width as i64).contains(&column);
+    }
+
+    pub fn render(&self) -> String {
+        self.pixels
+            .chunks(self.width.max(1))
+            .take(self.height)
+            .map(|row| {
+                row.iter()
+                    .map(|lit| if *lit { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+// What the CPU is doing during a cycle, passed to the tracer. The registers are as they are during the cycle, so
+// don't yet include the effect of an instruction finishing at the end of it.
+pub struct Trace<'a> {
+    pub cycle: u64,
+    pub registers: &'a Registers,
+    pub opcode: &'static str,
+    pub line: usize,
+}
+
+type Tracer<'a> = Box<dyn FnMut(&Trace) + 'a>;
+
+pub struct Cpu<'a> {
+    instruction_set: InstructionSet,
+    program: Vec<Instruction>,
+    registers: Registers,
+    crt: Crt,
+    // Cycles completed so far.
+    cycle: u64,
+    // The instruction being executed, and how many of its cycles have passed.
+    pc: usize,
+    elapsed: u32,
+    tracer: Option<Tracer<'a>>,
+    breakpoints: Vec<Condition>,
+}
+
+impl<'a> Cpu<'a> {
+    pub fn new(instruction_set: InstructionSet, program: Vec<Instruction>, crt: Crt) -> Self {
+        Cpu {
+            instruction_set,
+            program,
+            registers: Registers::new(),
+            crt,
+            cycle: 0,
+            pc: 0,
+            elapsed: 0,
+            tracer: None,
+            breakpoints: vec![],
+        }
+    }
+
+    pub fn set_tracer(&mut self, tracer: impl FnMut(&Trace) + 'a) {
+        self.tracer = Some(Box::new(tracer));
+    }
+
+    // Stop `run` when a condition on the inspected values holds, e.g. `X > 30` or `cycle = 100`.
+    pub fn add_breakpoint(&mut self, condition: Condition) {
+        self.breakpoints.push(condition);
+    }
+
+    pub fn crt(&self) -> &Crt {
+        &self.crt
+    }
+
+    // Run until the program finishes (returning None) or a breakpoint holds (returning its index). Runs at least one
+    // cycle, so calling it again continues past the breakpoint it last stopped at.
+    pub fn run(&mut self) -> Option<usize> {
+        while self.step() {
+            if let Some(idx) = self
+                .breakpoints
+                .iter()
+                .position(|breakpoint| breakpoint.holds(self))
+            {
+                return Some(idx);
+            }
+        }
+        None
+    }
+}
+
+impl Simulation for Cpu<'_> {
+    // Run a single cycle. Returns false once the program has finished.
+    fn step(&mut self) -> bool {
+        let Some(instruction) = self.program.get(self.pc) else {
+            return false;
+        };
+        let cycle = self.cycle + 1;
+        let opcode = &self.instruction_set.opcodes[instruction.opcode];
+        if let Some(tracer) = self.tracer.as_mut() {
+            tracer(&Trace {
+                cycle,
+                registers: &self.registers,
+                opcode: opcode.name,
+                line: instruction.line,
+            });
+        }
+        self.crt.draw(cycle, self.registers.get("X"));
+        self.elapsed += 1;
+        if self.elapsed == opcode.cycles {
+            (opcode.execute)(&mut self.registers, &instruction.operands);
+            self.pc += 1;
+            self.elapsed = 0;
+        }
+        self.cycle = cycle;
+        true
+    }
+
+    fn snapshot(&self) -> String {
+        self.crt.render()
+    }
+
+    // The cycle is the one about to run, so the registers are as they'll be during it.
+    fn inspect(&self) -> Vec<(String, String)> {
+        let mut values = vec![("cycle".to_string(), (self.cycle + 1).to_string())];
+        if let Some(instruction) = self.program.get(self.pc) {
+            values.push(("line".to_string(), instruction.line.to_string()));
+        }
+        values.extend(
+            self.registers
+                .values
+                .iter()
+                .map(|(name, value)| (name.clone(), value.to_string())),
+        );
+        values
+    }
+}
+
+// Going the other way: working out a program of addx and noop that makes the CRT draw a given picture.
+pub mod assembler {
+    use std::fmt;
+
+    use super::{Cpu, Crt, InstructionSet};
+    use crate::simulation::Simulation;
+
+    // The puzzle's block letters, each 4 pixels wide and 6 high. Letters are drawn with a blank column after each.
+    const FONT: &[(char, [&str; 6])] = &[
+        ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+        ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+        ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+        ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+        ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+        ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+        ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+        ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+        ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+        ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+        ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+        ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+        ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+        ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+        (' ', ["....", "....", "....", "....", "....", "...."]),
+    ];
+
+    #[derive(Debug, PartialEq)]
+    pub enum AssemblyError {
+        UnknownLetter(char),
+        TooWide { needed: usize, width: usize },
+        TooShort(usize),
+        Impossible { row: usize, column: usize },
+        Mismatch,
+    }
+
+    impl fmt::Display for AssemblyError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                AssemblyError::UnknownLetter(letter) => {
+                    write!(f, "there's no block letter for {}", letter)
+                }
+                AssemblyError::TooWide { needed, width } => write!(
+                    f,
+                    "the text needs {} columns but the screen only has {}",
+                    needed, width
+                ),
+                AssemblyError::TooShort(height) => write!(
+                    f,
+                    "the text needs 6 rows but the screen only has {}",
+                    height
+                ),
+                AssemblyError::Impossible { row, column } => write!(
+                    f,
+                    "no program can get the pixel at row {}, column {} right",
+                    row, column
+                ),
+                AssemblyError::Mismatch => write!(f, "the program doesn't draw the picture"),
+            }
+        }
+    }
+
+    // Lay out text in block letters from the top left of a screen of the given size. Pixels are true where lit.
+    pub fn render_text(
+        text: &str,
+        width: usize,
+        height: usize,
+    ) -> Result<Vec<Vec<bool>>, AssemblyError> {
+        let needed = (text.chars().count() * 5).saturating_sub(1);
+        if needed > width {
+            return Err(AssemblyError::TooWide { needed, width });
+        }
+        if height < 6 {
+            return Err(AssemblyError::TooShort(height));
+        }
+        let mut image = vec![vec![false; width]; height];
+        for (idx, letter) in text.chars().enumerate() {
+            let (_, glyph) = FONT
+                .iter()
+                .find(|(known, _)| *known == letter.to_ascii_uppercase())
+                .ok_or(AssemblyError::UnknownLetter(letter))?;
+            for (row, line) in glyph.iter().enumerate() {
+                for (column, pixel) in line.chars().enumerate() {
+                    image[row][idx * 5 + column] = pixel == '#';
+                }
+            }
+        }
+        Ok(image)
+    }
+
+    // Plan the value of X for every cycle so the sprite (sprite_width pixels wide) lights exactly the pixels wanted,
+    // and write the program that sets it. X only changes at the end of an addx, which holds it for two cycles first,
+    // so this is a search over (cycle, X) for the fewest instructions. X only needs trying a little way either side of
+    // the screen, as anywhere further off lights nothing just the same. The program is checked by running it.
+    pub fn assemble(image: &[Vec<bool>], sprite_width: usize) -> Result<String, AssemblyError> {
+        let width = image.first().map_or(0, Vec::len);
+        let cycles = width * image.len();
+        let half = sprite_width as i64 / 2;
+        let draws_right = |cycle: usize, x: i64| {
+            let column = (cycle % width) as i64;
+            let covered = (x - (sprite_width as i64 - 1) / 2..=x + half).contains(&column);
+            covered == image[cycle / width][cycle % width]
+        };
+        let xs =
+            (-(sprite_width as i64) - 1..=width as i64 + sprite_width as i64).collect::<Vec<i64>>();
+        let start = xs.iter().position(|x| *x == 1).unwrap();
+
+        // For each cycle boundary and X, the fewest instructions to get there and the instruction that did it last.
+        let mut best: Vec<Vec<Option<(usize, usize, usize)>>> =
+            vec![vec![None; xs.len()]; cycles + 1];
+        best[0][start] = Some((0, 0, start));
+        let mut furthest = 0;
+        for cycle in 0..cycles {
+            for idx in 0..xs.len() {
+                let Some((count, _, _)) = best[cycle][idx] else {
+                    continue;
+                };
+                furthest = cycle;
+                if !draws_right(cycle, xs[idx]) {
+                    continue;
+                }
+                let mut reach = |next: usize, next_idx: usize| {
+                    let state = &mut best[next][next_idx];
+                    if state.is_none_or(|(best_count, _, _)| count + 1 < best_count) {
+                        *state = Some((count + 1, cycle, idx));
+                    }
+                };
+                reach(cycle + 1, idx);
+                if cycle + 1 < cycles && draws_right(cycle + 1, xs[idx]) {
+                    for next_idx in 0..xs.len() {
+                        reach(cycle + 2, next_idx);
+                    }
+                }
+            }
+        }
+        let mut end_idx = (0..xs.len())
+            .filter(|idx| best[cycles][*idx].is_some())
+            .min_by_key(|idx| best[cycles][*idx].unwrap().0)
+            .ok_or(AssemblyError::Impossible {
+                row: furthest / width,
+                column: furthest % width,
+            })?;
+
+        let mut instructions = vec![];
+        let mut cycle = cycles;
+        while cycle > 0 {
+            let (_, previous, previous_idx) = best[cycle][end_idx].unwrap();
+            instructions.push(match cycle - previous {
+                1 => "noop".to_string(),
+                _ => format!("addx {}", xs[end_idx] - xs[previous_idx]),
+            });
+            (cycle, end_idx) = (previous, previous_idx);
+        }
+        instructions.reverse();
+        let program = instructions.join("\n");
+
+        let instruction_set = InstructionSet::standard();
+        let parsed = instruction_set.parse(&program).unwrap();
+        let mut cpu = Cpu::new(
+            instruction_set,
+            parsed,
+            Crt::new(width, image.len(), sprite_width),
+        );
+        while cpu.step() {}
+        let wanted = image
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|lit| if *lit { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+        match cpu.crt().render() == wanted {
+            true => Ok(program),
+            false => Err(AssemblyError::Mismatch),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_day10_part1_case1() {
+        assert_eq!(
+            day10(
+                "addx 15
+addx -11
+addx 6
+addx -3
+addx 5
+addx -1
+addx -8
+addx 13
+addx 4
+noop
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx -35
+addx 1
+addx 24
+addx -19
+addx 1
+addx 16
+addx -11
+noop
+noop
+addx 21
+addx -15
+noop
+noop
+addx -3
+addx 9
+addx 1
+addx -3
+addx 8
+addx 1
+addx 5
+noop
+noop
+noop
+noop
+noop
+addx -36
+noop
+addx 1
+addx 7
+noop
+noop
+noop
+addx 2
+addx 6
+noop
+noop
+noop
+noop
+noop
+addx 1
+noop
+noop
+addx 7
+addx 1
+noop
+addx -13
+addx 13
+addx 7
+noop
+addx 1
+addx -33
+noop
+noop
+noop
+addx 2
+noop
+noop
+noop
+addx 8
+noop
+addx -1
+addx 2
+addx 1
+noop
+addx 17
+addx -9
+addx 1
+addx 1
+addx -3
+addx 11
+noop
+noop
+addx 1
+noop
+addx 1
+noop
+noop
+addx -13
+addx -19
+addx 1
+addx 3
+addx 26
+addx -30
+addx 12
+addx -1
+addx 3
+addx 1
+noop
+noop
+noop
+addx -9
+addx 18
+addx 1
+addx 2
+noop
+noop
+addx 9
+noop
+noop
+noop
+addx -1
+addx 2
+addx -37
+addx 1
+addx 3
+noop
+addx 15
+addx -21
+addx 22
+addx -6
+addx 1
+noop
+addx 2
+addx 1
+noop
+addx -10
+noop
+noop
+addx 20
+addx 1
+addx 2
+addx 2
+addx -6
+addx -11
+noop
+noop
+noop"
+            )
+            .0,
+            "13140".to_string()
+        )
+    }
+
+    #[test]
+    fn check_day10_part2_case1() {
+        assert_eq!(
+            day10(
+                "addx 15
+addx -11
+addx 6
+addx -3
+addx 5
+addx -1
+addx -8
+addx 13
+addx 4
+noop
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx -35
+addx 1
+addx 24
+addx -19
+addx 1
+addx 16
+addx -11
+noop
+noop
+addx 21
+addx -15
+noop
+noop
+addx -3
+addx 9
+addx 1
+addx -3
+addx 8
+addx 1
+addx 5
+noop
+noop
+noop
+noop
+noop
+addx -36
+noop
+addx 1
+addx 7
+noop
+noop
+noop
+addx 2
+addx 6
+noop
+noop
+noop
+noop
+noop
+addx 1
+noop
+noop
+addx 7
+addx 1
+noop
+addx -13
+addx 13
+addx 7
+noop
+addx 1
+addx -33
+noop
+noop
+noop
+addx 2
+noop
+noop
+noop
+addx 8
+noop
+addx -1
+addx 2
+addx 1
+noop
+addx 17
+addx -9
+addx 1
+addx 1
+addx -3
+addx 11
+noop
+noop
+addx 1
+noop
+addx 1
+noop
+noop
+addx -13
+addx -19
+addx 1
+addx 3
+addx 26
+addx -30
+addx 12
+addx -1
+addx 3
+addx 1
+noop
+noop
+noop
+addx -9
+addx 18
+addx 1
+addx 2
+noop
+noop
+addx 9
+noop
+noop
+noop
+addx -1
+addx 2
+addx -37
+addx 1
+addx 3
+noop
+addx 15
+addx -21
+addx 22
+addx -6
+addx 1
+noop
+addx 2
+addx 1
+noop
+addx -10
+noop
+noop
+addx 20
+addx 1
+addx 2
+addx 2
+addx -6
+addx -11
+noop
+noop
+noop"
+            )
+            .1,
+            "##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######....."
+                .to_string()
+        )
+    }
+
+    #[test]
+    fn check_day10_both_case1() {
+        assert_eq!(
+            day10(
+                "addx 15
+addx -11
+addx 6
+addx -3
+addx 5
+addx -1
+addx -8
+addx 13
+addx 4
+noop
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx -35
+addx 1
+addx 24
+addx -19
+addx 1
+addx 16
+addx -11
+noop
+noop
+addx 21
+addx -15
+noop
+noop
+addx -3
+addx 9
+addx 1
+addx -3
+addx 8
+addx 1
+addx 5
+noop
+noop
+noop
+noop
+noop
+addx -36
+noop
+addx 1
+addx 7
+noop
+noop
+noop
+addx 2
+addx 6
+noop
+noop
+noop
+noop
+noop
+addx 1
+noop
+noop
+addx 7
+addx 1
+noop
+addx -13
+addx 13
+addx 7
+noop
+addx 1
+addx -33
+noop
+noop
+noop
+addx 2
+noop
+noop
+noop
+addx 8
+noop
+addx -1
+addx 2
+addx 1
+noop
+addx 17
+addx -9
+addx 1
+addx 1
+addx -3
+addx 11
+noop
+noop
+addx 1
+noop
+addx 1
+noop
+noop
+addx -13
+addx -19
+addx 1
+addx 3
+addx 26
+addx -30
+addx 12
+addx -1
+addx 3
+addx 1
+noop
+noop
+noop
+addx -9
+addx 18
+addx 1
+addx 2
+noop
+noop
+addx 9
+noop
+noop
+noop
+addx -1
+addx 2
+addx -37
+addx 1
+addx 3
+noop
+addx 15
+addx -21
+addx 22
+addx -6
+addx 1
+noop
+addx 2
+addx 1
+noop
+addx -10
+noop
+noop
+addx 20
+addx 1
+addx 2
+addx 2
+addx -6
+addx -11
+noop
+noop
+noop"
+            ),
+            (
+                "13140".to_string(),
+                "##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######....."
+                    .to_string()
+            )
+        )
+    }
+
+    #[test]
+    fn check_day10_cpu() {
+        let instruction_set = InstructionSet::standard().with(Opcode {
+            name: "set",
+            cycles: 3,
+            operands: 2,
+            execute: |registers, operands| {
+                if let Operand::Register(name) = &operands[0] {
+                    registers.set(name, registers.value(&operands[1]));
+                }
+            },
+        });
+        let program = instruction_set.parse("set Y 5\naddx Y\nnoop").unwrap();
+
+        let mut during = vec![];
+        let mut cpu = Cpu::new(instruction_set.clone(), program.clone(), Crt::new(3, 2, 1));
+        cpu.set_tracer(|trace| during.push((trace.cycle, trace.opcode, trace.registers.get("X"))));
+        assert_eq!(cpu.run(), None);
+        assert_eq!(cpu.crt().render(), ".#.\n.#.");
+        drop(cpu);
+        assert_eq!(
+            during,
+            vec![
+                (1, "set", 1),
+                (2, "set", 1),
+                (3, "set", 1),
+                (4, "addx", 1),
+                (5, "addx", 1),
+                (6, "noop", 6)
+            ]
+        );
+
+        let mut cpu = Cpu::new(instruction_set, program, Crt::new(40, 6, 3));
+        cpu.add_breakpoint(Condition::from_str("Y = 5").unwrap());
+        assert_eq!(cpu.run(), Some(0));
+        assert!(cpu
+            .inspect()
+            .contains(&("cycle".to_string(), "4".to_string())));
+    }
+
+    #[test]
+    fn check_day10_simulation() {
+        let instruction_set = InstructionSet::standard();
+        let program = instruction_set.parse("noop\naddx 3\naddx -5").unwrap();
+        let mut cpu = Cpu::new(instruction_set, program, Crt::new(3, 2, 1));
+        assert_eq!(
+            crate::simulation::run_until(&mut cpu, |_| false, 100),
+            Err(5)
+        );
+        assert!(!cpu.step());
+        assert_eq!(cpu.snapshot(), ".#.\n...");
+        assert!(cpu.inspect().contains(&("X".to_string(), "-1".to_string())));
+    }
+
+    #[test]
+    fn check_day10_parse_errors() {
+        let instruction_set = InstructionSet::standard();
+        for (program, error) in [
+            ("noop\njmp 3", "Line 2: unknown instruction jmp"),
+            ("addx", "Line 1: expected 1 operand(s)"),
+            (
+                "addx 1.5",
+                "Line 1: expected a number or register name, not 1.5",
+            ),
+        ] {
+            assert_eq!(
+                instruction_set.parse(program).err().unwrap().to_string(),
+                error
+            );
+        }
+    }
+
+    #[test]
+    fn check_day10_assembler() {
+        // The program really does draw the letters when run as a normal puzzle input.
+        let image = assembler::render_text("BPJAZGAP", 40, 6).unwrap();
+        let program = assembler::assemble(&image, 3).unwrap();
+        assert_eq!(
+            day10(&program).1,
+            "###..###....##..##..####..##...##..###..
+#..#.#..#....#.#..#....#.#..#.#..#.#..#.
+###..#..#....#.#..#...#..#....#..#.#..#.
+#..#.###.....#.####..#...#.##.####.###..
+#..#.#....#..#.#..#.#....#..#.#..#.#....
+###..#.....##..#..#.####..###.#..#.#...."
+        );
+
+        // X can't change between the two pixels without an addx spanning them, so a lone unlit pixel next to X is
+        // beyond any program.
+        assert_eq!(
+            assembler::assemble(&[vec![true, false, true]], 3),
+            Err(assembler::AssemblyError::Impossible { row: 0, column: 1 })
+        );
+        assert_eq!(
+            assembler::render_text("X", 40, 6),
+            Err(assembler::AssemblyError::UnknownLetter('X'))
+        );
+        assert_eq!(
+            assembler::render_text("ABCEFGHJ", 38, 6),
+            Err(assembler::AssemblyError::TooWide {
+                needed: 39,
+                width: 38
+            })
+        );
+        assert_eq!(
+            assembler::render_text("A", 4, 5),
+            Err(assembler::AssemblyError::TooShort(5))
+        );
+
+        // Taller screens get blank rows below the text.
+        let args = ["width=5", "height=8", "assemble=E"].map(String::from);
+        let program = day10_report("", &args);
+        let instruction_set = InstructionSet::standard();
+        let mut cpu = Cpu::new(
+            instruction_set.clone(),
+            instruction_set.parse(&program).unwrap(),
+            Crt::new(5, 8, 3),
+        );
+        while cpu.step() {}
+        assert_eq!(
+            cpu.snapshot(),
+            "####.\n#....\n###..\n#....\n#....\n####.\n.....\n....."
+        );
+    }
+}
@@ -0,0 +1,1090 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Rem;
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, digit1, multispace0},
+    combinator::map,
+    error::ParseError,
+    multi::{fold_many0, many1, separated_list1},
+    sequence::{delimited, pair},
+    IResult, Parser,
+};
+
+use itertools::Itertools;
+use num_bigint::BigUint;
+use num_traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::simulation::Simulation;
+use crate::state::{self, StateError};
+
+pub fn day11(input_lines: &str) -> (String, String) {
+    let pt1_monkeys = run_simulation(input_lines, 20, 3);
+    let answer1 = pt1_monkeys[0].inspection_count * pt1_monkeys[1].inspection_count;
+    let answer2 = monkey_business(
+        &MonkeySimulation::from_notes(input_lines, 1)
+            .unwrap_or_else(|error| panic!("{}", error))
+            .inspections_after(10000),
+    );
+    (format!("{}", answer1), format!("{}", answer2))
+}
+
+pub fn day11_simulation(input_lines: &str, part: u8) -> Box<dyn Simulation> {
+    let relief_factor = if part == 2 { 1 } else { 3 };
+    Box::new(
+        MonkeySimulation::from_notes(input_lines, relief_factor)
+            .unwrap_or_else(|error| panic!("{}", error)),
+    )
+}
+
+// `cargo run -- report 11 [rounds=n] [relief=r] [exact]` works out how many items each monkey inspects in n rounds (20
+// by default) when dividing worry by r after each inspection (3 by default). Without relief (r=1) and with operations
+// that only add and multiply, any number of rounds is quick as items' paths repeat. Otherwise every round is run with
+// the real worry levels, which outgrow a u64 within a hundred or so rounds. With `exact` the rounds are always run
+// keeping the real worry levels, however big, and what every monkey ends up holding is listed too. That's only
+// practical for a few hundred rounds.
+pub fn day11_report(input_lines: &str, args: &[String]) -> String {
+    let (mut rounds, mut relief_factor, mut exact) = (20, 3, false);
+    for arg in args {
+        if arg == "exact" {
+            exact = true;
+            continue;
+        }
+        let (name, value) = arg
+            .split_once('=')
+            .unwrap_or_else(|| panic!("Expected an option like rounds=20, not {}", arg));
+        let value = value
+            .parse::<u64>()
+            .unwrap_or_else(|_| panic!("Expected a number in {}", arg));
+        match name {
+            "rounds" => rounds = value,
+            "relief" => relief_factor = value,
+            _ => panic!("Unknown option {}", arg),
+        }
+    }
+    if exact {
+        let mut simulation = MonkeySimulation::exact(input_lines, relief_factor);
+        (0..rounds).for_each(|_| simulation.run_round());
+        return simulation.report();
+    }
+    let inspections = MonkeySimulation::from_notes(input_lines, relief_factor)
+        .unwrap_or_else(|error| panic!("{}", error))
+        .inspections_after(rounds);
+    let mut report = String::new();
+    for (id, count) in inspections.iter().enumerate() {
+        report.push_str(&format!("Monkey {} inspected {} items\n", id, count));
+    }
+    report + &format!("Monkey business: {}\n", monkey_business(&inspections))
+}
+
+// The product of the two largest inspection counts, which can get too big for a u64 over enough rounds.
+fn monkey_business(inspections: &[u64]) -> u128 {
+    let mut inspections = inspections.to_vec();
+    inspections.sort_unstable_by(|a, b| b.cmp(a));
+    inspections[0] as u128 * inspections[1] as u128
+}
+
+// A worry level. Plain u64s are enough when worry is kept small by the divisor product, while BigUints track it exactly.
+pub trait Worry:
+    Clone
+    + PartialEq
+    + fmt::Display
+    + From<u64>
+    + CheckedAdd
+    + CheckedSub
+    + CheckedMul
+    + CheckedDiv
+    + Rem<Output = Self>
+{
+}
+
+impl<T> Worry for T where
+    T: Clone
+        + PartialEq
+        + fmt::Display
+        + From<u64>
+        + CheckedAdd
+        + CheckedSub
+        + CheckedMul
+        + CheckedDiv
+        + Rem<Output = T>
+{
+}
+
+#[derive(Debug)]
+pub enum MonkeyError {
+    UnfilterableOperation { monkey: usize, operation: String },
+    FilterTooLarge(Vec<u64>),
+}
+
+impl fmt::Display for MonkeyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MonkeyError::UnfilterableOperation { monkey, operation } => write!(
+                f,
+                "Monkey {}: can't keep worry down through new = {} as it doesn't just add and multiply, try exact",
+                monkey, operation
+            ),
+            MonkeyError::FilterTooLarge(divisors) => write!(
+                f,
+                "can't keep worry down as the divisors {} multiply to more than 2^32, try exact",
+                divisors.iter().join(", ")
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Monkey<W = u64> {
+    id: usize,
+    items: Vec<W>,
+    operation: Expression,
+    test_divisor: u64,
+    throw_true: usize,
+    throw_false: usize,
+    inspection_count: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+}
+
+impl Operator {
+    pub fn symbol(&self) -> char {
+        match self {
+            Operator::Add => '+',
+            Operator::Subtract => '-',
+            Operator::Multiply => '*',
+            Operator::Divide => '/',
+            Operator::Remainder => '%',
+        }
+    }
+
+    fn apply<W: Worry>(&self, left: W, right: W) -> Option<W> {
+        match self {
+            Operator::Add => left.checked_add(&right),
+            Operator::Subtract => left.checked_sub(&right),
+            Operator::Multiply => left.checked_mul(&right),
+            Operator::Divide => left.checked_div(&right),
+            Operator::Remainder => (right != W::from(0)).then(|| left % right),
+        }
+    }
+
+    // Multiplication and division bind tighter than addition and subtraction, as usual.
+    fn precedence(&self) -> u8 {
+        match self {
+            Operator::Add | Operator::Subtract => 1,
+            _ => 2,
+        }
+    }
+}
+
+// A monkey's operation, e.g. "old * 19" or "(old + 3) * old % 7". Saved as the text it prints as, and parsed back from
+// that when loading.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expression {
+    Old,
+    Constant(u64),
+    Binary(Box<Expression>, Operator, Box<Expression>),
+}
+
+impl Expression {
+    // None if the worry would go below zero, get too big for W, or be divided by zero.
+    pub fn evaluate<W: Worry>(&self, old: &W) -> Option<W> {
+        match self {
+            Expression::Old => Some(old.clone()),
+            Expression::Constant(value) => Some(W::from(*value)),
+            Expression::Binary(left, operator, right) => {
+                operator.apply(left.evaluate(old)?, right.evaluate(old)?)
+            }
+        }
+    }
+
+    // Evaluate reducing modulo the modulus at every step, so the worry never gets much past the modulus squared. For
+    // modular expressions that's the result of evaluate modulo the modulus.
+    pub fn evaluate_modulo<W: Worry>(&self, old: &W, modulus: &W) -> Option<W> {
+        let value = match self {
+            Expression::Old => old.clone(),
+            Expression::Constant(value) => W::from(*value),
+            Expression::Binary(left, operator, right) => operator.apply(
+                left.evaluate_modulo(old, modulus)?,
+                right.evaluate_modulo(old, modulus)?,
+            )?,
+        };
+        (*modulus != W::from(0)).then(|| value % modulus.clone())
+    }
+
+    // Whether the expression only adds and multiplies, so that reducing old modulo some number first leaves the result
+    // the same modulo that number. Subtraction could go below zero, and division and remainders don't keep residues.
+    pub fn is_modular(&self) -> bool {
+        match self {
+            Expression::Binary(left, operator, right) => {
+                matches!(operator, Operator::Add | Operator::Multiply)
+                    && left.is_modular()
+                    && right.is_modular()
+            }
+            _ => true,
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Expression::Binary(_, operator, _) => operator.precedence(),
+            _ => u8::MAX,
+        }
+    }
+}
+
+// Prints with only the parentheses needed. Operators are left associative, so a right operand of the same precedence
+// keeps its parentheses: "old - (old - 1)" isn't "old - old - 1".
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expression::Old => write!(f, "old"),
+            Expression::Constant(value) => write!(f, "{}", value),
+            Expression::Binary(left, operator, right) => {
+                let precedence = operator.precedence();
+                match left.precedence() < precedence {
+                    true => write!(f, "({})", left)?,
+                    false => write!(f, "{}", left)?,
+                }
+                write!(f, " {} ", operator.symbol())?;
+                match right.precedence() <= precedence {
+                    true => write!(f, "({})", right),
+                    false => write!(f, "{}", right),
+                }
+            }
+        }
+    }
+}
+
+impl Serialize for Expression {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Expression {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let expression = String::deserialize(deserializer)?;
+        match parse_expression(&expression) {
+            Ok(("", operation)) => Ok(operation),
+            _ => Err(de::Error::custom(format!(
+                "invalid operation: {}",
+                expression
+            ))),
+        }
+    }
+}
+
+impl<W: Worry> Monkey<W> {
+    pub fn test(&self, item: &W) -> bool {
+        item.clone() % W::from(self.test_divisor) == W::from(0)
+    }
+
+    // Without a worry filter, worry is tracked exactly and grows without limit.
+    pub fn inspect_and_throw(
+        &mut self,
+        relief_factor: u64,
+        worry_filter: Option<u64>,
+    ) -> Vec<(usize, W)> {
+        let mut thrown_items = Vec::new();
+        while let Some(item) = self.items.pop() {
+            self.inspection_count += 1;
+            let item = match worry_filter {
+                Some(worry_filter) => self
+                    .operation
+                    .evaluate_modulo(&item, &W::from(worry_filter)),
+                None => self.operation.evaluate(&item),
+            }
+            .and_then(|worry| worry.checked_div(&W::from(relief_factor)))
+            .unwrap_or_else(|| {
+                panic!(
+                    "Monkey {}: worry went out of range working out {} for old = {}",
+                    self.id, self.operation, item
+                )
+            });
+            if self.test(&item) {
+                thrown_items.push((self.throw_true, item));
+            } else {
+                thrown_items.push((self.throw_false, item));
+            }
+        }
+        thrown_items
+    }
+}
+
+pub fn run_simulation(input_lines: &str, rounds: i32, relief_factor: u64) -> Vec<Monkey> {
+    let mut simulation = MonkeySimulation::from_notes(input_lines, relief_factor)
+        .unwrap_or_else(|error| panic!("{}", error));
+    for _round in 0..rounds {
+        simulation.run_round();
+    }
+    let mut monkeys = simulation.monkeys;
+    monkeys.sort_unstable_by(|a, b| b.inspection_count.cmp(&a.inspection_count));
+    monkeys
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MonkeySimulation<W = u64> {
+    monkeys: Vec<Monkey<W>>,
+    relief_factor: u64,
+    // What worry is reduced modulo before each inspection, or None when tracking it exactly.
+    worry_filter: Option<u64>,
+    rounds: u32,
+}
+
+impl MonkeySimulation {
+    // Without relief, worry is filtered to keep it down, which only works for operations that add and multiply.
+    pub fn from_notes(input_lines: &str, relief_factor: u64) -> Result<Self, MonkeyError> {
+        let mut simulation = Self::parse_notes(input_lines, relief_factor);
+        // All tests are "is divisible", we can therefore work out a "filter" where if the worry would pass all
+        // tests, we can throw that amount of worry away. Applying the filter looks like taking the modulo of the worry
+        // with the filter. The filter is the product of the divisors.
+        // e.g. if divisors are 3 & 5, filter is 15. Applying div 5 or div 3 tests to anything % filter is the same as
+        // applying it directly (e.g. if worry is 32, filtered worry is 32 & 15 = 2. 32 % 3 = 2, 2 % 3 = 2, 32 % 5 = 2, 2 % 5 = 2)
+        // That doesn't survive dividing by the relief factor though ((32 % 15) / 3 isn't 32 / 3 % 15), nor subtracting,
+        // dividing or taking remainders in operations. With relief, worry stays small enough for a while anyway.
+        // Operations are worked out modulo the filter a step at a time, so it has to be small enough for the product of
+        // two filtered values to fit in a u64.
+        if relief_factor == 1 {
+            if let Some(monkey) = simulation
+                .monkeys
+                .iter()
+                .find(|monkey| !monkey.operation.is_modular())
+            {
+                return Err(MonkeyError::UnfilterableOperation {
+                    monkey: monkey.id,
+                    operation: monkey.operation.to_string(),
+                });
+            }
+            let divisors = simulation
+                .monkeys
+                .iter()
+                .map(|m| m.test_divisor)
+                .collect::<Vec<u64>>();
+            let worry_filter = divisors
+                .iter()
+                .try_fold(1_u64, |filter, divisor| filter.checked_mul(*divisor))
+                .filter(|filter| *filter <= 1 << 32)
+                .ok_or_else(|| MonkeyError::FilterTooLarge(divisors.clone()))?;
+            simulation.worry_filter = Some(worry_filter);
+        }
+        Ok(simulation)
+    }
+
+    // How many items each monkey will have inspected after this many more rounds. Items never affect each other, and
+    // without relief and with operations that only add and multiply, an item's state at the start of a round is just
+    // which monkey holds it and its worry modulo the filter. There are only so many of those, so each item's path
+    // around the monkeys eventually repeats, and once it has the inspections it adds in any number of rounds can be
+    // worked out without running them. Relief or other operations lose that, so then the rounds are run on a copy.
+    pub fn inspections_after(&self, rounds: u64) -> Vec<u64> {
+        let worry_filter = match self.worry_filter {
+            Some(worry_filter)
+                if self.relief_factor == 1
+                    && self
+                        .monkeys
+                        .iter()
+                        .all(|monkey| monkey.operation.is_modular()) =>
+            {
+                worry_filter
+            }
+            _ => {
+                let mut simulation = self.clone();
+                (0..rounds).for_each(|_| simulation.run_round());
+                return simulation
+                    .monkeys
+                    .iter()
+                    .map(|monkey| monkey.inspection_count)
+                    .collect();
+            }
+        };
+        let mut totals = self
+            .monkeys
+            .iter()
+            .map(|monkey| monkey.inspection_count)
+            .collect::<Vec<u64>>();
+        for (holder, monkey) in self.monkeys.iter().enumerate() {
+            for item in &monkey.items {
+                let counts = self.follow_item((holder, item % worry_filter), rounds);
+                totals
+                    .iter_mut()
+                    .zip(counts)
+                    .for_each(|(total, count)| *total += count);
+            }
+        }
+        totals
+    }
+
+    fn follow_item(&self, mut state: (usize, u64), rounds: u64) -> Vec<u64> {
+        // The round each state was first seen at, and the inspections by each monkey before every round so far.
+        let mut seen = HashMap::new();
+        let mut history = vec![vec![0; self.monkeys.len()]];
+        loop {
+            let round = history.len() - 1;
+            if round as u64 == rounds {
+                return history.pop().unwrap();
+            }
+            if let Some(start) = seen.insert(state, round) {
+                let (cycles, remainder) = (
+                    (rounds - start as u64) / (round - start) as u64,
+                    (rounds - start as u64) % (round - start) as u64,
+                );
+                return (0..self.monkeys.len())
+                    .map(|idx| {
+                        history[start + remainder as usize][idx]
+                            + cycles * (history[round][idx] - history[start][idx])
+                    })
+                    .collect();
+            }
+            let mut counts = history[round].clone();
+            state = self.follow_round(state, &mut counts);
+            history.push(counts);
+        }
+    }
+
+    // Pass an item from monkey to monkey for a round, counting the inspections. A monkey only gets to an item in the
+    // same round if it comes after the one throwing it, otherwise it waits until the next.
+    fn follow_round(
+        &self,
+        (mut holder, mut worry): (usize, u64),
+        counts: &mut [u64],
+    ) -> (usize, u64) {
+        let worry_filter = self.worry_filter.unwrap();
+        loop {
+            let monkey = &self.monkeys[holder];
+            counts[holder] += 1;
+            // There's no relief here, and the filter is small enough for the operations to stay in range.
+            worry = monkey
+                .operation
+                .evaluate_modulo(&worry, &worry_filter)
+                .expect("Filtered worry shouldn't go out of range");
+            let destination = match monkey.test(&worry) {
+                true => monkey.throw_true,
+                false => monkey.throw_false,
+            };
+            if destination <= holder {
+                return (destination, worry);
+            }
+            holder = destination;
+        }
+    }
+}
+
+impl MonkeySimulation<BigUint> {
+    // Keep the real worry levels, however big they get.
+    pub fn exact(input_lines: &str, relief_factor: u64) -> Self {
+        Self::parse_notes(input_lines, relief_factor)
+    }
+}
+
+impl<W: Worry> MonkeySimulation<W> {
+    fn parse_notes(input_lines: &str, relief_factor: u64) -> Self {
+        let (_, mut monkeys) = many1(parse_monkey)(input_lines).unwrap();
+        monkeys.sort_unstable_by(|a, b| a.id.cmp(&b.id));
+        assert_eq!(monkeys[0].id, 0);
+        Self {
+            monkeys,
+            relief_factor,
+            worry_filter: None,
+            rounds: 0,
+        }
+    }
+
+    pub fn run_round(&mut self) {
+        for monkey_idx in 0..self.monkeys.len() {
+            for (destination, item) in
+                self.monkeys[monkey_idx].inspect_and_throw(self.relief_factor, self.worry_filter)
+            {
+                self.monkeys[destination].items.push(item);
+            }
+        }
+        self.rounds += 1;
+    }
+
+    fn report(&self) -> String {
+        self.monkeys
+            .iter()
+            .map(|monkey| {
+                format!(
+                    "Monkey {} (new = {}) inspected {} items and holds: {}\n",
+                    monkey.id,
+                    monkey.operation,
+                    monkey.inspection_count,
+                    monkey.items.iter().map(|item| item.to_string()).join(", ")
+                )
+            })
+            .collect()
+    }
+}
+
+impl Simulation for MonkeySimulation {
+    fn step(&mut self) -> bool {
+        // The monkeys never stop throwing.
+        self.run_round();
+        true
+    }
+
+    fn snapshot(&self) -> String {
+        self.monkeys
+            .iter()
+            .map(|monkey| {
+                format!(
+                    "Monkey {}: {}\n",
+                    monkey.id,
+                    monkey.items.iter().map(|item| item.to_string()).join(", ")
+                )
+            })
+            .collect()
+    }
+
+    fn inspect(&self) -> Vec<(String, String)> {
+        let mut values = vec![("rounds".to_string(), self.rounds.to_string())];
+        values.extend(self.monkeys.iter().map(|monkey| {
+            (
+                format!("monkey_{}_inspections", monkey.id),
+                monkey.inspection_count.to_string(),
+            )
+        }));
+        values
+    }
+
+    fn save_state(&self) -> Result<String, StateError> {
+        state::to_json(self)
+    }
+
+    fn load_state(&mut self, json: &str) -> Result<(), StateError> {
+        *self = state::from_json(json)?;
+        Ok(())
+    }
+}
+
+pub fn parse_monkey<W: Worry>(buf: &str) -> IResult<&str, Monkey<W>> {
+    let (buf, _) = remove_surr_whitespace(tag("Monkey ")).parse(buf)?;
+    let (buf, id) = digit1(buf)?;
+    let (buf, _) = char(':')(buf)?;
+    let (buf, _) = remove_surr_whitespace(tag("Starting items:")).parse(buf)?;
+    let (buf, items) = remove_surr_whitespace(separated_list1(tag(", "), digit1)).parse(buf)?;
+    let (buf, operation) = parse_operation(buf)?;
+    let (buf, _) = remove_surr_whitespace(tag("Test: divisible by ")).parse(buf)?;
+    let (buf, test_divisor) = digit1(buf)?;
+    let (buf, _) = remove_surr_whitespace(tag("If true: throw to monkey ")).parse(buf)?;
+    let (buf, throw_true) = digit1(buf)?;
+    let (buf, _) = remove_surr_whitespace(tag("If false: throw to monkey ")).parse(buf)?;
+    let (buf, throw_false) = remove_surr_whitespace(digit1).parse(buf)?;
+    Ok((
+        buf,
+        Monkey {
+            id: id.parse::<usize>().unwrap(),
+            items: items
+                .iter()
+                .map(|item| W::from(item.parse::<u64>().unwrap()))
+                .collect(),
+            operation,
+            test_divisor: test_divisor.parse::<u64>().unwrap(),
+            throw_true: throw_true.parse::<usize>().unwrap(),
+            throw_false: throw_false.parse::<usize>().unwrap(),
+            inspection_count: 0,
+        },
+    ))
+}
+
+pub fn parse_operation(buf: &str) -> IResult<&str, Expression> {
+    let (buf, _) = remove_surr_whitespace(tag("Operation: new = ")).parse(buf)?;
+    parse_expression(buf)
+}
+
+// Sums of products of `old`, numbers and bracketed expressions, e.g. "(old + 3) * old % 7".
+pub fn parse_expression(buf: &str) -> IResult<&str, Expression> {
+    let (buf, first) = parse_term(buf)?;
+    fold_many0(
+        pair(
+            remove_surr_whitespace(alt((
+                map(char('+'), |_| Operator::Add),
+                map(char('-'), |_| Operator::Subtract),
+            ))),
+            parse_term,
+        ),
+        move || first.clone(),
+        |left, (operator, right)| Expression::Binary(Box::new(left), operator, Box::new(right)),
+    )(buf)
+}
+
+fn parse_term(buf: &str) -> IResult<&str, Expression> {
+    let (buf, first) = parse_factor(buf)?;
+    fold_many0(
+        pair(
+            remove_surr_whitespace(alt((
+                map(char('*'), |_| Operator::Multiply),
+                map(char('/'), |_| Operator::Divide),
+                map(char('%'), |_| Operator::Remainder),
+            ))),
+            parse_factor,
+        ),
+        move || first.clone(),
+        |left, (operator, right)| Expression::Binary(Box::new(left), operator, Box::new(right)),
+    )(buf)
+}
+
+fn parse_factor(buf: &str) -> IResult<&str, Expression> {
+    alt((
+        map(tag("old"), |_| Expression::Old),
+        map(digit1, |digits: &str| {
+            Expression::Constant(digits.parse::<u64>().unwrap())
+        }),
+        delimited(
+            pair(char('('), multispace0),
+            parse_expression,
+            pair(multispace0, char(')')),
+        ),
+    ))(buf)
+}
+
+// For a parser 'inner' return a parser that consumes (and discards) leading and trailing whitespace and newlines either side of 'inner'
+pub fn remove_surr_whitespace<'a, O, E: ParseError<&'a str>, F>(
+    inner: F,
+) -> impl Parser<&'a str, O, E>
+where
+    F: Parser<&'a str, O, E>,
+{
+    delimited(multispace0, inner, multispace0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_NOTES: &str = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1";
+
+    #[test]
+    fn check_day11_part1_case1() {
+        assert_eq!(
+            day11(
+                "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1"
+            )
+            .0,
+            "10605".to_string()
+        )
+    }
+
+    #[test]
+    fn check_day11_part2_case1() {
+        assert_eq!(
+            day11(
+                "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1"
+            )
+            .1,
+            "2713310158".to_string()
+        )
+    }
+
+    #[test]
+    fn check_day11_both_case1() {
+        assert_eq!(
+            day11(
+                "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1"
+            ),
+            ("10605".to_string(), "2713310158".to_string())
+        )
+    }
+
+    #[test]
+    fn check_day11_simulation() {
+        let mut simulation = MonkeySimulation::from_notes(EXAMPLE_NOTES, 3).unwrap();
+        simulation.step();
+        assert_eq!(
+            simulation.snapshot(),
+            "Monkey 0: 26, 27, 23, 20
+Monkey 1: 2080, 401, 1046, 167, 207, 25
+Monkey 2: \nMonkey 3: \n"
+        );
+        assert_eq!(
+            crate::simulation::run_until(&mut simulation, |s| s.rounds == 20, 100),
+            Ok(19)
+        );
+        assert!(simulation
+            .inspect()
+            .contains(&("monkey_3_inspections".to_string(), "105".to_string())));
+    }
+
+    #[test]
+    fn check_day11_state_round_trip() {
+        let mut simulation = MonkeySimulation::from_notes(EXAMPLE_NOTES, 1).unwrap();
+        for _ in 0..5000 {
+            simulation.run_round();
+        }
+        let json = simulation.save_state().unwrap();
+        assert!(json.contains("\"operation\":\"old * old\""));
+        let mut resumed = MonkeySimulation::from_notes(EXAMPLE_NOTES, 3).unwrap();
+        resumed.load_state(&json).unwrap();
+        assert_eq!(resumed.snapshot(), simulation.snapshot());
+        for _ in 0..5000 {
+            resumed.run_round();
+        }
+        let mut counts = resumed
+            .monkeys
+            .iter()
+            .map(|monkey| monkey.inspection_count)
+            .collect::<Vec<u64>>();
+        counts.sort_unstable();
+        assert_eq!(counts, vec![1938, 47830, 52013, 52166]);
+
+        let monkeys: Vec<Monkey> =
+            state::from_json(&state::to_json(&resumed.monkeys).unwrap()).unwrap();
+        assert_eq!(monkeys[2].operation.evaluate(&7u64).unwrap(), 49);
+        assert!(matches!(
+            state::from_json::<Expression>("\"old ^ 2\""),
+            Err(StateError::Json(_))
+        ));
+    }
+
+    #[test]
+    fn check_day11_expression() {
+        for (text, printed, value) in [
+            ("old * 19", "old * 19", 133),
+            ("(old + 3) * old % 10", "(old + 3) * old % 10", 0),
+            ("((old)) - 2 - 1", "old - 2 - 1", 4),
+            ("old - (2 - 1)", "old - (2 - 1)", 6),
+            ("old*(old+1)/ 2", "old * (old + 1) / 2", 28),
+        ] {
+            let (rest, expression) = parse_expression(text).unwrap();
+            assert_eq!(rest, "");
+            assert_eq!(expression.to_string(), printed);
+            assert_eq!(expression.evaluate(&7u64).unwrap(), value);
+            assert_eq!(parse_expression(printed).unwrap(), ("", expression.clone()));
+            assert_eq!(
+                state::from_json::<Expression>(&state::to_json(&expression).unwrap()).unwrap(),
+                expression
+            );
+        }
+        let (_, square) = parse_expression("old * old").unwrap();
+        assert_eq!(
+            square
+                .evaluate(&BigUint::from(u64::MAX))
+                .unwrap()
+                .to_string(),
+            "340282366920938463426481119284349108225"
+        );
+    }
+
+    #[test]
+    fn check_day11_exact() {
+        // After a round of the example the real worry levels are those from the puzzle text.
+        let mut exact = MonkeySimulation::exact(EXAMPLE_NOTES, 3);
+        exact.run_round();
+        assert_eq!(
+            exact.report().lines().nth(1).unwrap(),
+            "Monkey 1 (new = old + 6) inspected 4 items and holds: 2080, 401, 1046, 167, 207, 25"
+        );
+
+        // Without relief the real levels get huge, but the inspections match those of the filtered simulation.
+        let mut exact = MonkeySimulation::exact(EXAMPLE_NOTES, 1);
+        let mut filtered = MonkeySimulation::from_notes(EXAMPLE_NOTES, 1).unwrap();
+        for _ in 0..20 {
+            exact.run_round();
+            filtered.run_round();
+        }
+        assert_eq!(
+            exact
+                .monkeys
+                .iter()
+                .map(|monkey| monkey.inspection_count)
+                .collect::<Vec<u64>>(),
+            vec![99, 97, 8, 103]
+        );
+        assert_eq!(
+            filtered.inspect()[1..],
+            exact
+                .monkeys
+                .iter()
+                .map(|monkey| (
+                    format!("monkey_{}_inspections", monkey.id),
+                    monkey.inspection_count.to_string()
+                ))
+                .collect::<Vec<(String, String)>>()
+        );
+        assert!(exact
+            .monkeys
+            .iter()
+            .flat_map(|monkey| &monkey.items)
+            .any(|item| item.bits() > 64));
+    }
+
+    #[test]
+    fn check_day11_inspections_after() {
+        // Skipping ahead agrees with running the rounds, from the start or part way through.
+        let mut simulation = MonkeySimulation::from_notes(EXAMPLE_NOTES, 1).unwrap();
+        for round in 0..300 {
+            let skipped = MonkeySimulation::from_notes(EXAMPLE_NOTES, 1)
+                .unwrap()
+                .inspections_after(round);
+            let counts = simulation
+                .monkeys
+                .iter()
+                .map(|monkey| monkey.inspection_count)
+                .collect::<Vec<u64>>();
+            assert_eq!(skipped, counts, "round {}", round);
+            assert_eq!(
+                simulation.inspections_after(300 - round),
+                MonkeySimulation::from_notes(EXAMPLE_NOTES, 1)
+                    .unwrap()
+                    .inspections_after(300)
+            );
+            simulation.run_round();
+        }
+
+        let simulation = MonkeySimulation::from_notes(EXAMPLE_NOTES, 1).unwrap();
+        assert_eq!(
+            monkey_business(&simulation.inspections_after(10000)),
+            2713310158
+        );
+        let inspections = simulation.inspections_after(1_000_000_000_000);
+        assert!(inspections.iter().sum::<u64>() >= 10 * 1_000_000_000_000);
+        assert!(monkey_business(&inspections) > u64::MAX as u128);
+
+        // Following items works operations out modulo the filter a step at a time too, so cubing is fine, but the
+        // filter has to be small enough for that to stay in range.
+        let cubing = EXAMPLE_NOTES
+            .replace("old * 19", "old * old * old")
+            .replace("divisible by 23", "divisible by 9973");
+        let mut simulation = MonkeySimulation::from_notes(&cubing, 1).unwrap();
+        let skipped = simulation.inspections_after(500);
+        (0..500).for_each(|_| simulation.run_round());
+        assert_eq!(
+            skipped,
+            simulation
+                .monkeys
+                .iter()
+                .map(|monkey| monkey.inspection_count)
+                .collect::<Vec<u64>>()
+        );
+        let too_big = EXAMPLE_NOTES.replace("divisible by 23", "divisible by 10000019");
+        assert_eq!(
+            MonkeySimulation::from_notes(&too_big, 1)
+                .err()
+                .unwrap()
+                .to_string(),
+            "can't keep worry down as the divisors 10000019, 19, 13, 17 multiply to more than 2^32, try exact"
+        );
+        let overflowing = EXAMPLE_NOTES
+            .replace("divisible by 23", "divisible by 4294967311")
+            .replace("divisible by 19", "divisible by 4294967357");
+        assert!(matches!(
+            MonkeySimulation::from_notes(&overflowing, 1),
+            Err(MonkeyError::FilterTooLarge(_))
+        ));
+        assert!(MonkeySimulation::from_notes(&overflowing, 3).is_ok());
+    }
+
+    #[test]
+    fn check_day11_inspections_after_with_relief() {
+        // Relief mixes up residues, so the rounds are run as usual, agreeing with running them directly and with
+        // following the real worry levels.
+        let dividing = EXAMPLE_NOTES
+            .replace("old * 19", "old * 7 - 1")
+            .replace("old + 6", "old / 2 + 1");
+        for notes in [EXAMPLE_NOTES, &dividing] {
+            let mut direct = MonkeySimulation::from_notes(notes, 3).unwrap();
+            let mut exact = MonkeySimulation::exact(notes, 3);
+            for _ in 0..60 {
+                direct.run_round();
+                exact.run_round();
+            }
+            let skipped = MonkeySimulation::from_notes(notes, 3)
+                .unwrap()
+                .inspections_after(60);
+            for simulation_counts in [
+                direct
+                    .monkeys
+                    .iter()
+                    .map(|monkey| monkey.inspection_count)
+                    .collect::<Vec<u64>>(),
+                exact
+                    .monkeys
+                    .iter()
+                    .map(|monkey| monkey.inspection_count)
+                    .collect::<Vec<u64>>(),
+            ] {
+                assert_eq!(skipped, simulation_counts);
+            }
+        }
+    }
+
+    #[test]
+    fn check_day11_filtering() {
+        // Filtered worry can't be relied on through subtraction or division, so they're refused without relief, while
+        // exact worry handles them.
+        let dividing = EXAMPLE_NOTES
+            .replace("old * 19", "old * 7 - 1")
+            .replace("old + 6", "old / 2 + 1");
+        assert_eq!(
+            MonkeySimulation::from_notes(&dividing, 1)
+                .err()
+                .unwrap()
+                .to_string(),
+            "Monkey 0: can't keep worry down through new = old * 7 - 1 as it doesn't just add and multiply, try exact"
+        );
+        let mut exact = MonkeySimulation::exact(&dividing, 1);
+        let mut unfiltered = MonkeySimulation::<u64>::parse_notes(&dividing, 1);
+        for _ in 0..4 {
+            exact.run_round();
+            unfiltered.run_round();
+        }
+        assert_eq!(exact.report(), unfiltered.report());
+
+        for (text, old) in [
+            ("old - 10", 3),
+            ("old % (old - 3)", 3),
+            ("old * old", u64::MAX),
+        ] {
+            let (_, expression) = parse_expression(text).unwrap();
+            assert_eq!(expression.evaluate(&old), None, "{}", text);
+            assert_eq!(expression.is_modular(), text == "old * old");
+        }
+
+        // Filtered worry is reduced after every step of an operation, so cubing it stays in range without relief even
+        // with a filter too big to cube, and the inspections match those of the real worry levels.
+        let cubing = EXAMPLE_NOTES
+            .replace("old * 19", "old * old * old")
+            .replace("divisible by 23", "divisible by 9973");
+        let mut exact = MonkeySimulation::exact(&cubing, 1);
+        let mut filtered = MonkeySimulation::from_notes(&cubing, 1).unwrap();
+        for _ in 0..5 {
+            exact.run_round();
+            filtered.run_round();
+        }
+        assert_eq!(
+            filtered
+                .monkeys
+                .iter()
+                .map(|monkey| monkey.inspection_count)
+                .collect::<Vec<u64>>(),
+            exact
+                .monkeys
+                .iter()
+                .map(|monkey| monkey.inspection_count)
+                .collect::<Vec<u64>>()
+        );
+        for _ in 0..1000 {
+            filtered.run_round();
+        }
+        let (_, cube) = parse_expression("old * old * old").unwrap();
+        assert_eq!(cube.evaluate(&(1u64 << 32)), None);
+        assert_eq!(
+            cube.evaluate_modulo(&(1u64 << 32), &((1u64 << 32) - 1)),
+            Some(1)
+        );
+    }
+}
@@ -1,12 +1,19 @@
 // Decided on HashMap over vectors filled with air to reduce memory usage at cost of speed. Would be simple to tweak based on requirements.
 use std::{collections::HashMap, i32};
 
+use serde::{Deserialize, Serialize};
+
+use crate::simulation::Simulation;
+use crate::state::{self, StateError};
+
+const SAND_SOURCE: (i32, i32) = (500, 0);
+
 pub fn day14(input_lines: &str) -> (String, String) {
     let mut cave = Cave::from_path_scan(input_lines, None);
     cave.print();
     let mut answer1 = 0;
     loop {
-        match cave.add_sand((500, 0)) {
+        match cave.add_sand(SAND_SOURCE) {
             Ok(_) => {
                 answer1 += 1;
             }
@@ -18,7 +25,7 @@ pub fn day14(input_lines: &str) -> (String, String) {
     let mut cave2 = Cave::from_path_scan(input_lines, Some(2));
     let mut answer2 = 0;
     loop {
-        match cave2.add_sand((500, 0)) {
+        match cave2.add_sand(SAND_SOURCE) {
             Ok(_) => {
                 answer2 += 1;
             }
@@ -29,12 +36,20 @@ pub fn day14(input_lines: &str) -> (String, String) {
     (format!("{}", answer1), format!("{}", answer2))
 }
 
+pub fn day14_simulation(input_lines: &str, part: u8) -> Box<dyn Simulation> {
+    let floor = if part == 2 { Some(2) } else { None };
+    Box::new(Cave::from_path_scan(input_lines, floor))
+}
+
+#[derive(Serialize, Deserialize)]
 pub struct Cave {
+    #[serde(with = "crate::state::map_as_pairs")]
     map: HashMap<(i32, i32), TileType>,
     min_x: i32,
     max_x: i32,
     max_y: i32,
     floor: Option<i32>,
+    settled_sand: u32,
 }
 
 impl Cave {
@@ -45,6 +60,7 @@ impl Cave {
             max_x: i32::MIN,
             max_y: 0,
             floor: None,
+            settled_sand: 0,
         }
     }
 
@@ -130,7 +146,10 @@ impl Cave {
         loop {
             match self.move_sand(current) {
                 Ok(next) => current = next,
-                Err(PlacementError::Blocked(_)) => return Ok(current),
+                Err(PlacementError::Blocked(_)) => {
+                    self.settled_sand += 1;
+                    return Ok(current);
+                }
                 Err(PlacementError::Abyss(e)) => return Err(PlacementError::Abyss(e)),
             }
         }
@@ -159,28 +178,51 @@ impl Cave {
     }
 
     pub fn print(&self) {
-        let max_y = if self.floor.is_some() {
-            self.max_y + self.floor.unwrap()
-        } else {
-            self.max_y
-        };
+        print!("{}", self.snapshot());
+    }
+}
+
+impl Simulation for Cave {
+    fn step(&mut self) -> bool {
+        self.add_sand(SAND_SOURCE).is_ok()
+    }
+
+    fn snapshot(&self) -> String {
+        let max_y = self.max_y + self.floor.unwrap_or(0);
+        let mut picture = String::new();
         for y in 0..=max_y {
             for x in self.min_x..=self.max_x {
-                let tile = self.get_tile(x, y);
-                let c = match tile {
+                picture.push(match self.get_tile(x, y) {
                     TileType::Rock => '#',
                     TileType::Sand => 'o',
                     TileType::Air => '.',
                     TileType::Abyss => panic!("We should never be in the abyss!"),
-                };
-                print!("{}", c);
+                });
             }
-            println!();
+            picture.push('\n');
         }
+        picture
+    }
+
+    fn inspect(&self) -> Vec<(String, String)> {
+        vec![
+            ("sand".to_string(), self.settled_sand.to_string()),
+            ("lowest_rock".to_string(), self.max_y.to_string()),
+            ("floor".to_string(), format!("{:?}", self.floor)),
+        ]
+    }
+
+    fn save_state(&self) -> Result<String, StateError> {
+        state::to_json(self)
+    }
+
+    fn load_state(&mut self, json: &str) -> Result<(), StateError> {
+        *self = state::from_json(json)?;
+        Ok(())
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TileType {
     Abyss,
     Air,
@@ -232,4 +274,37 @@ mod tests {
             ("24".to_string(), "93".to_string())
         )
     }
+
+    #[test]
+    fn check_day14_simulation() {
+        let mut cave = Cave::from_path_scan(
+            "498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9",
+            None,
+        );
+        assert_eq!(
+            crate::simulation::run_until(&mut cave, |c| c.settled_sand == 5, 100),
+            Ok(5)
+        );
+        assert!(cave.snapshot().ends_with("....oooo#.\n#########.\n"));
+        while cave.step() {}
+        assert!(cave
+            .inspect()
+            .contains(&("sand".to_string(), "24".to_string())));
+    }
+
+    #[test]
+    fn check_day14_state_round_trip() {
+        let scan = "498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9";
+        let mut cave = Cave::from_path_scan(scan, Some(2));
+        for _ in 0..10 {
+            cave.step();
+        }
+        let mut resumed = Cave::from_path_scan("", None);
+        resumed.load_state(&cave.save_state().unwrap()).unwrap();
+        assert_eq!(resumed.snapshot(), cave.snapshot());
+        while resumed.step() {}
+        assert_eq!(resumed.settled_sand, 93);
+    }
 }
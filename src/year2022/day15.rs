@@ -298,6 +298,53 @@ impl SensorMap {
     }
 }
 
+#[cfg(feature = "bench")]
+pub mod benchmarks {
+    use super::*;
+    use crate::benchmarks::InputGenerator;
+    use criterion::{black_box, BenchmarkId, Criterion};
+
+    const SENSOR_SPACING: i32 = 100_000;
+
+    pub fn register(c: &mut Criterion) {
+        let mut generator = InputGenerator::new(15);
+        let mut group = c.benchmark_group("day15/search_level");
+        for sensor_count in [8, 32, 128] {
+            let map = SensorMap::from_sensor_output(&sensor_report(&mut generator, sensor_count));
+            let max_x = sensor_count as i32 * SENSOR_SPACING;
+            group.bench_with_input(BenchmarkId::from_parameter(sensor_count), &map, |b, map| {
+                b.iter(|| {
+                    map.search_level(black_box(0), vec![GridSquare::Beacon], Some(0), Some(max_x))
+                })
+            });
+        }
+        group.finish();
+    }
+
+    // Sensors strung out along row 0, each reaching well past its neighbours so the whole row is covered (as it is in
+    // the real input). Uncovered points are stepped over one at a time, which would swamp the timings.
+    fn sensor_report(generator: &mut InputGenerator, sensor_count: usize) -> String {
+        (0..sensor_count as i32)
+            .map(|i| {
+                let spacing = SENSOR_SPACING as i64;
+                let x = i as i64 * spacing + generator.between(-spacing / 4, spacing / 4);
+                let y = generator.between(-spacing, spacing);
+                let range = 2 * spacing + y.abs() + generator.below(spacing as u64) as i64;
+                let beacon_dx = generator.between(-range, range);
+                let beacon_dy = (range - beacon_dx.abs()) * generator.choose(&[-1, 1]);
+                format!(
+                    "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+                    x,
+                    y,
+                    x + beacon_dx,
+                    y + beacon_dy
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -397,6 +397,83 @@ impl TunnelSystem {
     }
 }
 
+#[cfg(feature = "bench")]
+pub mod benchmarks {
+    use super::*;
+    use crate::benchmarks::InputGenerator;
+    use criterion::{BatchSize, BenchmarkId, Criterion};
+
+    pub fn register(c: &mut Criterion) {
+        let mut generator = InputGenerator::new(16);
+        let tunnel_system = TunnelSystem::from_scan(&valve_scan(&mut generator, 60));
+        let mut probe = Path::new("AA", 15, vec![], tunnel_system.max_flow, 1);
+        // More pressure than any earlier visit could reach, so every earlier visit has to be checked.
+        probe.pressure_released = 10000;
+
+        let mut group = c.benchmark_group("day16/is_worse_state");
+        for visit_count in [10, 100, 1000] {
+            let mut tunnel_system = tunnel_system.clone();
+            let prior_visits = (0..visit_count)
+                .map(|_| {
+                    (
+                        generator.below(1000) as u32,
+                        generator.below(100) as u32,
+                        generator.below(30) as u8,
+                    )
+                })
+                .collect();
+            tunnel_system
+                .exploration_states
+                .insert(vec!["AA".to_string()], prior_visits);
+            group.bench_with_input(
+                BenchmarkId::from_parameter(visit_count),
+                &tunnel_system,
+                |b, tunnel_system| {
+                    b.iter_batched(
+                        || tunnel_system.clone(),
+                        |mut tunnel_system| tunnel_system.is_worse_state(&probe),
+                        BatchSize::SmallInput,
+                    )
+                },
+            );
+        }
+        group.finish();
+    }
+
+    // A connected tunnel network in which about half the valves are worth opening. Valve AA, where the explorers
+    // start, never is.
+    fn valve_scan(generator: &mut InputGenerator, valve_count: usize) -> String {
+        let name = |i: usize| {
+            format!(
+                "{}{}",
+                (b'A' + (i / 26) as u8) as char,
+                (b'A' + (i % 26) as u8) as char
+            )
+        };
+        let mut tunnels = vec![vec![]; valve_count];
+        for i in 1..valve_count {
+            let neighbour = generator.below(i as u64) as usize;
+            tunnels[i].push(name(neighbour));
+            tunnels[neighbour].push(name(i));
+        }
+        (0..valve_count)
+            .map(|i| {
+                let flow_rate = match i {
+                    0 => 0,
+                    _ => generator.choose(&[0, 0, 0, 5, 10, 15, 20, 25]),
+                };
+                format!(
+                    "Valve {} has flow rate={}; tunnels lead to valves {}",
+                    name(i),
+                    flow_rate,
+                    tunnels[i].join(", ")
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
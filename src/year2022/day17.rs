@@ -3,20 +3,18 @@ use std::{
     iter::{repeat, Repeat},
 };
 
+use serde::{Deserialize, Serialize};
+
+use crate::simulation::Simulation;
+use crate::state::{self, StateError};
+
 const SURFACE_DEPTH: usize = 16;
 
+// The current wave of items, and a source of fresh waves once it runs out.
+type WaveGenerator<T> = (VecDeque<T>, Repeat<VecDeque<T>>);
+
 pub fn day17(input_lines: &str) -> (String, String) {
-    let mut chamber = Chamber::new(
-        7,
-        VecDeque::from([
-            RockType::HLine,
-            RockType::Cross,
-            RockType::LShape,
-            RockType::VLine,
-            RockType::Box,
-        ]),
-        Chamber::scan_jets(input_lines),
-    );
+    let mut chamber = Chamber::from_jet_scan(input_lines);
     chamber.add_rocks(2022);
     let answer1 = chamber.rock_height;
     // chamber.print();
@@ -25,6 +23,10 @@ pub fn day17(input_lines: &str) -> (String, String) {
     (format!("{}", answer1), format!("{}", answer2))
 }
 
+pub fn day17_simulation(input_lines: &str, _part: u8) -> Box<dyn Simulation> {
+    Box::new(Chamber::from_jet_scan(input_lines))
+}
+
 #[derive(Clone, Debug, PartialEq)]
 struct Rock {
     pub height: usize,
@@ -64,7 +66,7 @@ impl Rock {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum RockType {
     HLine,
     Cross,
@@ -73,20 +75,22 @@ pub enum RockType {
     Box,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Jet {
     Left,
     Right,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 struct Chamber {
     contents: Vec<u32>,
     rock_height: usize,
     chamber_width: usize,
     added_rocks: u64,
-    rock_generator: (VecDeque<RockType>, Repeat<VecDeque<RockType>>),
-    jet_generator: (VecDeque<Jet>, Repeat<VecDeque<Jet>>),
+    #[serde(with = "wave_generator")]
+    rock_generator: WaveGenerator<RockType>,
+    #[serde(with = "wave_generator")]
+    jet_generator: WaveGenerator<Jet>,
     empty_layer: u32,
     repeating_unit_layer_start: usize,
     repeating_unit_height: usize,
@@ -94,6 +98,33 @@ struct Chamber {
     repeating_unit_rock_count_start: u64,
 }
 
+// A Repeat can't be serialized, but it only ever yields copies of the wave it was built from, so store that wave
+// alongside what's left of the current one.
+mod wave_generator {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::VecDeque;
+    use std::iter::repeat;
+
+    use super::WaveGenerator;
+
+    pub fn serialize<T, S>(generator: &WaveGenerator<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize + Clone,
+        S: Serializer,
+    {
+        (&generator.0, generator.1.clone().next()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<WaveGenerator<T>, D::Error>
+    where
+        T: Deserialize<'de> + Clone,
+        D: Deserializer<'de>,
+    {
+        let (current_wave, wave) = <(VecDeque<T>, VecDeque<T>)>::deserialize(deserializer)?;
+        Ok((current_wave, repeat(wave)))
+    }
+}
+
 impl Chamber {
     pub fn new(width: usize, rock_wave: VecDeque<RockType>, jet_scan: VecDeque<Jet>) -> Self {
         // We're using bits as bits of space, with 1 as a rock and 0 as empty space. The floor and walls are all rock.
@@ -116,6 +147,20 @@ impl Chamber {
         }
     }
 
+    pub fn from_jet_scan(scan: &str) -> Self {
+        Chamber::new(
+            7,
+            VecDeque::from([
+                RockType::HLine,
+                RockType::Cross,
+                RockType::LShape,
+                RockType::VLine,
+                RockType::Box,
+            ]),
+            Chamber::scan_jets(scan),
+        )
+    }
+
     pub fn clear(&mut self) {
         self.contents = vec![self.contents[0]];
         self.rock_height = 0;
@@ -361,6 +406,17 @@ impl Chamber {
         generator.0.front()
     }
 
+    // Unlike peek_item, looks into the next wave if the current one has run out.
+    fn upcoming_item<T: Clone>(generator: &(VecDeque<T>, Repeat<VecDeque<T>>)) -> Option<T> {
+        Chamber::peek_item(generator).cloned().or_else(|| {
+            generator
+                .1
+                .clone()
+                .next()
+                .and_then(|wave| wave.front().cloned())
+        })
+    }
+
     fn current_state(&self) -> (&[u32], Option<&RockType>, Option<&Jet>) {
         let surface_depth = SURFACE_DEPTH.min(self.rock_height);
         (
@@ -371,16 +427,85 @@ impl Chamber {
     }
 
     fn print(&self) {
-        for row in self.contents.iter().rev() {
-            let row = format!("{:b}", row);
-            println!(
-                "{}",
-                row.chars()
+        println!("{}", self.snapshot());
+    }
+}
+
+impl Simulation for Chamber {
+    fn step(&mut self) -> bool {
+        // There's always another rock to drop.
+        self.add_rock(false);
+        true
+    }
+
+    fn snapshot(&self) -> String {
+        self.contents
+            .iter()
+            .rev()
+            .map(|row| {
+                format!("{:b}", row)
+                    .chars()
                     .map(|c| if c == '1' { '#' } else { '.' })
                     .collect::<String>()
+                    + "\n"
+            })
+            .collect()
+    }
+
+    fn inspect(&self) -> Vec<(String, String)> {
+        vec![
+            ("rocks".to_string(), self.added_rocks.to_string()),
+            ("height".to_string(), self.rock_height.to_string()),
+            (
+                "next_rock".to_string(),
+                format!("{:?}", Chamber::upcoming_item(&self.rock_generator)),
+            ),
+            (
+                "next_jet".to_string(),
+                format!("{:?}", Chamber::upcoming_item(&self.jet_generator)),
+            ),
+        ]
+    }
+
+    fn save_state(&self) -> Result<String, StateError> {
+        state::to_json(self)
+    }
+
+    fn load_state(&mut self, json: &str) -> Result<(), StateError> {
+        *self = state::from_json(json)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "bench")]
+pub mod benchmarks {
+    use super::*;
+    use crate::benchmarks::InputGenerator;
+    use criterion::{BatchSize, BenchmarkId, Criterion};
+
+    pub fn register(c: &mut Criterion) {
+        let mut generator = InputGenerator::new(17);
+        let jets = (0..10000)
+            .map(|_| generator.choose(&['<', '>']))
+            .collect::<String>();
+        // Dropping a rock should cost the same however tall the tower already is.
+        let mut group = c.benchmark_group("day17/add_rock");
+        for tower_rocks in [100, 1000, 10000] {
+            let mut chamber = Chamber::from_jet_scan(&jets);
+            chamber.add_rocks(tower_rocks);
+            group.bench_with_input(
+                BenchmarkId::from_parameter(tower_rocks),
+                &chamber,
+                |b, chamber| {
+                    b.iter_batched(
+                        || chamber.clone(),
+                        |mut chamber| chamber.add_rock(false),
+                        BatchSize::SmallInput,
+                    )
+                },
             );
         }
-        println!();
+        group.finish();
     }
 }
 
@@ -411,4 +536,33 @@ mod tests {
             ("3068".to_string(), "1514285714288".to_string())
         )
     }
+
+    #[test]
+    fn check_day17_simulation() {
+        let mut chamber = Chamber::from_jet_scan(">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>");
+        assert_eq!(
+            crate::simulation::run_until(&mut chamber, |c| c.added_rocks == 10, 100),
+            Ok(10)
+        );
+        assert!(chamber
+            .inspect()
+            .contains(&("height".to_string(), "17".to_string())));
+        assert!(chamber.snapshot().ends_with("#..####.#\n#########\n"));
+    }
+
+    #[test]
+    fn check_day17_state_round_trip() {
+        let mut chamber = Chamber::from_jet_scan(">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>");
+        chamber.add_rocks(1000);
+        let json = chamber.save_state().unwrap();
+        let mut resumed = Chamber::from_jet_scan("<");
+        resumed.load_state(&json).unwrap();
+        assert_eq!(resumed.inspect(), chamber.inspect());
+        resumed.add_rocks(1022);
+        assert_eq!(resumed.rock_height, 3068);
+        assert_eq!(
+            resumed.simulate_rocks(1000000000000 - resumed.added_rocks),
+            1514285714288
+        );
+    }
 }
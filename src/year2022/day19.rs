@@ -471,6 +471,33 @@ pub fn build(
     state
 }
 
+#[cfg(feature = "bench")]
+pub mod benchmarks {
+    use super::*;
+    use criterion::{BenchmarkId, Criterion};
+
+    pub fn register(c: &mut Criterion) {
+        let factory = RobotFactory::from_blueprint(
+            "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.",
+        );
+        // The number of purchase orders grows combinatorially with the size of the wallet.
+        let mut group = c.benchmark_group("day19/possible_robots");
+        for ore in [4, 8, 12, 16] {
+            let resources = Inventory::from_vec(vec![
+                (&Material::Ore, ore),
+                (&Material::Clay, 14),
+                (&Material::Obsidian, 7),
+            ]);
+            group.bench_with_input(
+                BenchmarkId::from_parameter(ore),
+                &resources,
+                |b, resources| b.iter(|| factory.possible_robots(resources)),
+            );
+        }
+        group.finish();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
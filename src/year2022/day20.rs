@@ -0,0 +1,325 @@
+use crate::simulation::Simulation;
+
+const DECRYPTION_KEY: i64 = 811589153;
+
+pub fn day20(input_lines: &str) -> (String, String) {
+    let mut encrypted_file = File::new(input_lines, 0, 1);
+    encrypted_file.mix();
+    let answer1 = encrypted_file.get_coordinates().iter().sum::<i64>();
+    let mut encrypted_file = File::new(input_lines, 0, DECRYPTION_KEY);
+    for _ in 0..10 {
+        encrypted_file.mix();
+    }
+    let answer2 = encrypted_file.get_coordinates().iter().sum::<i64>();
+    (format!("{}", answer1), format!("{}", answer2))
+}
+
+pub fn day20_simulation(input_lines: &str, part: u8) -> Box<dyn Simulation> {
+    let encryption_multiplier = if part == 2 { DECRYPTION_KEY } else { 1 };
+    Box::new(File::new(input_lines, 0, encryption_multiplier))
+}
+
+#[derive(Debug)]
+pub struct Coordinate {
+    value: i64,
+    previous_cd: usize,
+    next_cd: usize,
+    #[allow(dead_code)]
+    // Useful for debugging
+    original_index: usize,
+}
+
+#[derive(Debug)]
+pub struct File {
+    coordinates: Vec<Coordinate>,
+    start: usize,
+    marker_pos: usize,
+    // The next coordinate to move (by original index), and how many full mixes have been done.
+    mix_cursor: usize,
+    mix_count: usize,
+}
+
+impl File {
+    pub fn new(input_lines: &str, marker: i64, encryption_multiplier: i64) -> Self {
+        let len = input_lines.lines().count();
+        let mut marker_pos = 0;
+        Self {
+            coordinates: input_lines
+                .lines()
+                .enumerate()
+                .map(|(i, line)| {
+                    let value = line.parse::<i64>().unwrap() * encryption_multiplier;
+                    if value == marker {
+                        marker_pos = i
+                    }
+                    Coordinate {
+                        value,
+                        original_index: i,
+                        previous_cd: if i == 0 { len - 1 } else { i - 1 },
+                        next_cd: if i == len - 1 { 0 } else { i + 1 },
+                    }
+                })
+                .collect(),
+            start: 0,
+            marker_pos,
+            mix_cursor: 0,
+            mix_count: 0,
+        }
+    }
+
+    // Finish the current mix, which is a whole mix unless it's been partly stepped through.
+    pub fn mix(&mut self) {
+        self.mix_next();
+        while self.mix_cursor != 0 {
+            self.mix_next();
+        }
+    }
+
+    // Move the next coordinate in the mixing order.
+    fn mix_next(&mut self) {
+        let i = self.mix_cursor;
+        let current = &self.coordinates[i];
+        let next = current.next_cd;
+        let previous = current.previous_cd;
+        let new_previous = self.traverse_gaps(i, current.value);
+
+        if new_previous != i {
+            if self.start == i {
+                self.start = next;
+            }
+            self.coordinates[next].previous_cd = previous;
+            self.coordinates[previous].next_cd = next;
+            let new_next = self.coordinates[new_previous].next_cd;
+            self.coordinates[new_previous].next_cd = i;
+            self.coordinates[new_next].previous_cd = i;
+            self.coordinates[i].previous_cd = new_previous;
+            self.coordinates[i].next_cd = new_next;
+        }
+
+        self.mix_cursor = (self.mix_cursor + 1) % self.coordinates.len();
+        if self.mix_cursor == 0 {
+            self.mix_count += 1;
+        }
+    }
+
+    pub fn traverse_gaps(&self, start: usize, steps: i64) -> usize {
+        self.traverse(start, steps % (self.coordinates.len() as i64 - 1))
+    }
+
+    pub fn traverse(&self, start: usize, steps: i64) -> usize {
+        let move_distance = steps % self.coordinates.len() as i64;
+
+        let move_distance = if move_distance < 0 {
+            // We have to move an extra step to get the previous rather than next.
+            self.coordinates.len() as i64 + move_distance - 1
+        } else {
+            move_distance
+        } as usize;
+
+        let mut current = start;
+
+        if move_distance > self.coordinates.len() / 2 {
+            let move_distance = self.coordinates.len() - move_distance;
+            for _ in 0..move_distance {
+                current = self.coordinates[current].previous_cd;
+            }
+        } else {
+            for _ in 0..move_distance {
+                current = self.coordinates[current].next_cd;
+            }
+        }
+        current
+    }
+
+    pub fn vec_index(&self, index: usize) -> &Coordinate {
+        &self.coordinates[index]
+    }
+
+    pub fn get_coordinates(&self) -> Vec<i64> {
+        [1000, 2000, 3000]
+            .map(|value| self.vec_index(self.traverse(self.marker_pos, value)).value)
+            .to_vec()
+    }
+
+    #[allow(dead_code)]
+    pub fn print(&self) {
+        println!("{}", self.snapshot());
+    }
+}
+
+impl Simulation for File {
+    fn step(&mut self) -> bool {
+        // Mixing can be repeated as many times as we like, as long as there's something to mix.
+        if self.coordinates.is_empty() {
+            return false;
+        }
+        self.mix_next();
+        true
+    }
+
+    fn snapshot(&self) -> String {
+        let mut current = self.start;
+        let mut values = vec![];
+        for _ in 0..self.coordinates.len() {
+            values.push(self.coordinates[current].value.to_string());
+            current = self.coordinates[current].next_cd;
+        }
+        values.join(", ")
+    }
+
+    fn inspect(&self) -> Vec<(String, String)> {
+        let mut values = vec![
+            ("mixes".to_string(), self.mix_count.to_string()),
+            ("mix_position".to_string(), self.mix_cursor.to_string()),
+        ];
+        if !self.coordinates.is_empty() {
+            values.push((
+                "next_value".to_string(),
+                self.coordinates[self.mix_cursor].value.to_string(),
+            ));
+            values.push((
+                "grove_coordinates".to_string(),
+                self.get_coordinates().iter().sum::<i64>().to_string(),
+            ));
+        }
+        values
+    }
+}
+
+#[cfg(feature = "bench")]
+pub mod benchmarks {
+    use super::*;
+    use crate::benchmarks::InputGenerator;
+    use criterion::{black_box, BatchSize, BenchmarkId, Criterion};
+
+    pub fn register(c: &mut Criterion) {
+        let mut generator = InputGenerator::new(20);
+        let sizes = [100, 1000, 5000];
+        let files = sizes
+            .iter()
+            .map(|&size| (size, encrypted_file(&mut generator, size)))
+            .collect::<Vec<(usize, String)>>();
+
+        let mut group = c.benchmark_group("day20/mix");
+        for (size, file) in &files {
+            group.bench_with_input(BenchmarkId::from_parameter(size), file, |b, file| {
+                b.iter_batched(
+                    || File::new(file, 0, 1),
+                    |mut file| file.mix(),
+                    BatchSize::LargeInput,
+                )
+            });
+        }
+        group.finish();
+
+        // Traversing a third of the way round is close to the worst case, as anything past halfway goes backwards.
+        let mut group = c.benchmark_group("day20/traverse");
+        for (size, file) in &files {
+            let file = File::new(file, 0, 1);
+            group.bench_with_input(BenchmarkId::from_parameter(size), &file, |b, file| {
+                b.iter(|| file.traverse(0, black_box(*size as i64 / 3)))
+            });
+        }
+        group.finish();
+    }
+
+    // Numbers spread well beyond the length of the file, like the real input, with the single 0 the puzzle promises.
+    fn encrypted_file(generator: &mut InputGenerator, size: usize) -> String {
+        let mut numbers = (1..size)
+            .map(|_| match generator.between(-10000, 9999) {
+                0 => 10000,
+                number => number,
+            })
+            .collect::<Vec<i64>>();
+        numbers.insert(generator.below(size as u64) as usize, 0);
+        numbers
+            .iter()
+            .map(|number| number.to_string())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_day20_part1_case1() {
+        assert_eq!(
+            day20(
+                "1
+2
+-3
+3
+-2
+0
+4"
+            )
+            .0,
+            "3".to_string()
+        )
+    }
+
+    #[test]
+    fn check_day20_part2_case1() {
+        assert_eq!(
+            day20(
+                "1
+2
+-3
+3
+-2
+0
+4"
+            )
+            .1,
+            "1623178306".to_string()
+        )
+    }
+
+    #[test]
+    fn check_day20_both_case1() {
+        assert_eq!(
+            day20(
+                "1
+2
+-3
+3
+-2
+0
+4"
+            ),
+            ("3".to_string(), "1623178306".to_string())
+        )
+    }
+
+    #[test]
+    fn check_day20_simulation() {
+        let mut file = File::new(
+            "1
+2
+-3
+3
+-2
+0
+4",
+            0,
+            1,
+        );
+        assert_eq!(
+            crate::simulation::run_until(&mut file, |f| f.mix_count == 1, 100),
+            Ok(7)
+        );
+        assert_eq!(file.snapshot(), "1, 2, -3, 4, 0, 3, -2");
+        file.step();
+        file.mix();
+        assert_eq!(file.mix_count, 2);
+        assert_eq!(file.mix_cursor, 0);
+
+        let mut empty = File::new("", 0, 1);
+        assert!(!empty.step());
+        assert_eq!(empty.snapshot(), "");
+        assert_eq!(empty.inspect().len(), 2);
+    }
+}
@@ -2,14 +2,43 @@
 // structures. I might return to this and remove those bits and store the edge relations in an Outline struct along with the ordering etc.
 // instead.
 use std::cell::RefCell;
+use std::collections::VecDeque;
 use std::rc::{Rc, Weak};
 
+use crate::simulation::Simulation;
+
 pub fn day22(input_lines: &str) -> (String, String) {
     day22_main(input_lines, 50)
 }
 
 pub fn day22_main(input_lines: &str, face_size: usize) -> (String, String) {
     let (map, instruction_str) = input_lines.split_once("\n\n").unwrap();
+    let instructions = parse_instructions(instruction_str);
+
+    let board = BoardMap::from_str(map);
+    let mut device = InputDevice::new(board);
+    let answer1 = device.get_password(&instructions);
+    let cube = CubeMap::from_str(map, face_size);
+    let mut device = InputDevice::new(cube);
+    device.print();
+    let answer2 = device.get_password(&instructions);
+    (format!("{}", answer1), format!("{}", answer2))
+}
+
+pub fn day22_simulation(input_lines: &str, part: u8) -> Box<dyn Simulation> {
+    let (map, instruction_str) = input_lines.split_once("\n\n").unwrap();
+    if part == 2 {
+        let mut device = InputDevice::new(CubeMap::from_str(map, 50));
+        device.queue_instructions(parse_instructions(instruction_str));
+        Box::new(device)
+    } else {
+        let mut device = InputDevice::new(BoardMap::from_str(map));
+        device.queue_instructions(parse_instructions(instruction_str));
+        Box::new(device)
+    }
+}
+
+fn parse_instructions(instruction_str: &str) -> Vec<Instruction> {
     let mut instruction_str = instruction_str.chars().peekable();
     let mut instructions = vec![];
     while let Some(c) = instruction_str.peek() {
@@ -35,15 +64,7 @@ pub fn day22_main(input_lines: &str, face_size: usize) -> (String, String) {
             instructions.push(Instruction::Move(number.parse().unwrap()));
         }
     }
-
-    let board = BoardMap::from_str(map);
-    let mut device = InputDevice::new(board);
-    let answer1 = device.get_password(&instructions);
-    let cube = CubeMap::from_str(map, face_size);
-    let mut device = InputDevice::new(cube);
-    device.print();
-    let answer2 = device.get_password(&instructions);
-    (format!("{}", answer1), format!("{}", answer2))
+    instructions
 }
 
 #[derive(Clone, Copy)]
@@ -75,6 +96,8 @@ pub struct InputDevice<T: Map> {
     map: T,
     marker: (usize, usize),
     direction: (i32, i32),
+    // Instructions still to follow when stepping through as a simulation.
+    pending: VecDeque<Instruction>,
 }
 impl<T> InputDevice<T>
 where
@@ -90,18 +113,32 @@ where
             map,
             marker: (starter_x, 0),
             direction: (1, 0),
+            pending: VecDeque::new(),
         }
     }
 
+    pub fn queue_instructions(&mut self, instructions: Vec<Instruction>) {
+        self.pending.extend(instructions);
+    }
+
     pub fn get_password(&mut self, instructions: &Vec<Instruction>) -> usize {
         for instruction in instructions {
-            match instruction {
-                Instruction::Move(moves) => self.move_marker(*moves),
-                Instruction::TurnLeft => self.turn_marker(Instruction::TurnLeft),
-                Instruction::TurnRight => self.turn_marker(Instruction::TurnRight),
-            }
+            self.follow_instruction(instruction);
             // self.print();
         }
+        self.print();
+        self.password()
+    }
+
+    fn follow_instruction(&mut self, instruction: &Instruction) {
+        match instruction {
+            Instruction::Move(moves) => self.move_marker(*moves),
+            Instruction::TurnLeft => self.turn_marker(Instruction::TurnLeft),
+            Instruction::TurnRight => self.turn_marker(Instruction::TurnRight),
+        }
+    }
+
+    fn password(&self) -> usize {
         let facing = match self.direction {
             (1, 0) => 0,
             (0, 1) => 1,
@@ -109,7 +146,6 @@ where
             (0, -1) => 3,
             _ => panic!("Invalid direction"),
         };
-        self.print();
         1000 * (self.marker.1 + 1) + 4 * (self.marker.0 + 1) + facing
     }
 
@@ -156,26 +192,59 @@ where
     }
 
     pub fn print(&self) {
+        print!("{}", self.snapshot());
+    }
+}
+
+impl<T> Simulation for InputDevice<T>
+where
+    T: Map,
+{
+    fn step(&mut self) -> bool {
+        let Some(instruction) = self.pending.pop_front() else {
+            return false;
+        };
+        self.follow_instruction(&instruction);
+        true
+    }
+
+    fn snapshot(&self) -> String {
+        let mut picture = String::new();
         for y in 0..self.map.get_map_length() {
             for x in 0..self.map.get_map_width() {
-                if (x, y) == self.marker {
+                picture.push(if (x, y) == self.marker {
                     match self.direction {
-                        (1, 0) => print!(">"),
-                        (0, -1) => print!("^"),
-                        (-1, 0) => print!("<"),
-                        (0, 1) => print!("v"),
+                        (1, 0) => '>',
+                        (0, -1) => '^',
+                        (-1, 0) => '<',
+                        (0, 1) => 'v',
                         _ => panic!("Invalid direction"),
                     }
                 } else {
                     match self.map.get_tile(x, y) {
-                        Tile::Void => print!(" "),
-                        Tile::Open => print!("."),
-                        Tile::Solid => print!("#"),
+                        Tile::Void => ' ',
+                        Tile::Open => '.',
+                        Tile::Solid => '#',
                     }
-                }
+                });
             }
-            println!();
+            picture.push('\n');
         }
+        picture
+    }
+
+    fn inspect(&self) -> Vec<(String, String)> {
+        vec![
+            ("column".to_string(), (self.marker.0 + 1).to_string()),
+            ("row".to_string(), (self.marker.1 + 1).to_string()),
+            ("direction".to_string(), format!("{:?}", self.direction)),
+            ("password".to_string(), self.password().to_string()),
+            (
+                "next_instruction".to_string(),
+                format!("{:?}", self.pending.front()),
+            ),
+            ("remaining".to_string(), self.pending.len().to_string()),
+        ]
     }
 }
 
@@ -595,4 +664,33 @@ mod tests {
             ("6032".to_string(), "5031".to_string())
         )
     }
+
+    #[test]
+    fn check_day22_simulation() {
+        let (map, instructions) = "        ...#
+        .#..
+        #...
+        ....
+...#.......#
+........#...
+..#....#....
+..........#.
+        ...#....
+        .....#..
+        .#......
+        ......#.
+
+10R5L5R10L4R5L5"
+            .split_once("\n\n")
+            .unwrap();
+        let mut device = InputDevice::new(BoardMap::from_str(map));
+        device.queue_instructions(parse_instructions(instructions));
+        assert_eq!(
+            crate::simulation::run_until(&mut device, |d| d.direction == (0, 1), 10),
+            Ok(2)
+        );
+        assert!(device.snapshot().starts_with("        ..v#    \n"));
+        while device.step() {}
+        assert_eq!(device.password(), 6032);
+    }
 }
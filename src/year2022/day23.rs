@@ -1,13 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+use crate::simulation::Simulation;
+use crate::state::{self, StateError};
+
 pub fn day23(input_lines: &str) -> (String, String) {
-    let mut coordinator = Coordinator::from_str(
-        input_lines,
-        vec![
-            Direction::North,
-            Direction::South,
-            Direction::West,
-            Direction::East,
-        ],
-    );
+    let mut coordinator = Coordinator::from_str(input_lines, standard_consideration_order());
     coordinator.print_scoring_grid();
     coordinator.run_rounds(10);
     coordinator.print_scoring_grid();
@@ -18,14 +15,30 @@ pub fn day23(input_lines: &str) -> (String, String) {
     (format!("{}", answer1), format!("{}", answer2))
 }
 
-#[derive(Debug, Clone)]
+pub fn day23_simulation(input_lines: &str, _part: u8) -> Box<dyn Simulation> {
+    Box::new(Coordinator::from_str(
+        input_lines,
+        standard_consideration_order(),
+    ))
+}
+
+fn standard_consideration_order() -> Vec<Direction> {
+    vec![
+        Direction::North,
+        Direction::South,
+        Direction::West,
+        Direction::East,
+    ]
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Position {
     Elf,
     Empty,
     ProposedMove(Vec<(usize, usize)>),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub enum Direction {
     North,
     South,
@@ -33,6 +46,7 @@ pub enum Direction {
     West,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct Coordinator {
     grid: Vec<Vec<Position>>,
     elf_count: usize,
@@ -274,17 +288,7 @@ impl Coordinator {
 
     pub fn print_scoring_grid(&self) {
         println!("Scoring Grid:\n");
-        for y in self.northmost..=self.southmost {
-            for x in self.westmost..=self.eastmost {
-                match self.grid[y][x] {
-                    Position::Elf => print!("#"),
-                    Position::Empty => print!("."),
-                    Position::ProposedMove(_) => print!("?"), // Assuming '?' for proposed moves
-                }
-            }
-            println!();
-        }
-        println!();
+        println!("{}", self.snapshot());
     }
 
     pub fn print_grid(&self) {
@@ -303,6 +307,90 @@ impl Coordinator {
     }
 }
 
+impl Simulation for Coordinator {
+    fn step(&mut self) -> bool {
+        // Once no elf wants to move, nothing will ever change again.
+        self.run_round() > 0
+    }
+
+    fn snapshot(&self) -> String {
+        let mut picture = String::new();
+        for y in self.northmost..=self.southmost {
+            for x in self.westmost..=self.eastmost {
+                picture.push(match self.grid[y][x] {
+                    Position::Elf => '#',
+                    Position::Empty => '.',
+                    Position::ProposedMove(_) => '?', // Assuming '?' for proposed moves
+                });
+            }
+            picture.push('\n');
+        }
+        picture
+    }
+
+    fn inspect(&self) -> Vec<(String, String)> {
+        vec![
+            ("rounds".to_string(), self.round_count.to_string()),
+            ("elves".to_string(), self.elf_count.to_string()),
+            (
+                "empty_ground".to_string(),
+                self.progress_score().to_string(),
+            ),
+            (
+                "first_direction".to_string(),
+                format!("{:?}", self.consideration_order[self.first_consideration]),
+            ),
+        ]
+    }
+
+    fn save_state(&self) -> Result<String, StateError> {
+        state::to_json(self)
+    }
+
+    fn load_state(&mut self, json: &str) -> Result<(), StateError> {
+        *self = state::from_json(json)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "bench")]
+pub mod benchmarks {
+    use super::*;
+    use crate::benchmarks::InputGenerator;
+    use criterion::{BatchSize, BenchmarkId, Criterion};
+
+    pub fn register(c: &mut Criterion) {
+        let mut generator = InputGenerator::new(23);
+        let mut group = c.benchmark_group("day23/run_round");
+        for size in [10, 40, 160] {
+            let grove = grove_scan(&mut generator, size);
+            group.bench_with_input(BenchmarkId::from_parameter(size), &grove, |b, grove| {
+                b.iter_batched(
+                    || Coordinator::from_str(grove, standard_consideration_order()),
+                    |mut coordinator| coordinator.run_round(),
+                    BatchSize::LargeInput,
+                )
+            });
+        }
+        group.finish();
+    }
+
+    // A square of elves at about the real input's density. It's surrounded by a border of empty ground, so the first
+    // round doesn't have to reallocate (and print) the grid.
+    fn grove_scan(generator: &mut InputGenerator, size: usize) -> String {
+        let border = ".".repeat(size + 2);
+        let mut scan = border.clone();
+        for _ in 0..size {
+            scan.push_str("\n.");
+            scan.extend((0..size).map(|_| generator.choose(&['#', '.'])));
+            scan.push('.');
+        }
+        scan.push('\n');
+        scan.push_str(&border);
+        scan
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -372,4 +460,46 @@ mod tests {
             ("110".to_string(), "20".to_string())
         )
     }
+
+    #[test]
+    fn check_day23_simulation() {
+        let mut coordinator = Coordinator::from_str(
+            ".....
+..##.
+..#..
+.....
+..##.
+.....",
+            standard_consideration_order(),
+        );
+        assert_eq!(
+            crate::simulation::run_until(&mut coordinator, |c| c.round_count == 2, 10),
+            Ok(2)
+        );
+        assert_eq!(coordinator.snapshot(), ".##.\n#...\n...#\n....\n.#..\n");
+        while coordinator.step() {}
+        assert!(coordinator
+            .inspect()
+            .contains(&("rounds".to_string(), "4".to_string())));
+    }
+
+    #[test]
+    fn check_day23_state_round_trip() {
+        let mut coordinator = Coordinator::from_str(
+            "....#..
+..###.#
+#...#.#
+.#...##
+#.###..
+##.#.##
+.#..#..",
+            standard_consideration_order(),
+        );
+        coordinator.run_rounds(10);
+        let mut resumed: Coordinator =
+            crate::state::from_json(&coordinator.save_state().unwrap()).unwrap();
+        assert_eq!(resumed.progress_score(), 110);
+        resumed.run_to_completion();
+        assert_eq!(resumed.round_count, 20);
+    }
 }
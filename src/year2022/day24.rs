@@ -1,5 +1,7 @@
 use std::collections::{HashMap, VecDeque};
 
+use serde::{Deserialize, Serialize};
+
 pub fn day24(input: &str) -> (String, String) {
     day24_main(input, false)
 }
@@ -19,7 +21,7 @@ pub fn day24_main(input_lines: &str, debug: bool) -> (String, String) {
     (format!("{}", answer1), format!("{}", answer2))
 }
 
-#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub enum Blizzard {
     North,
     South,
@@ -27,14 +29,14 @@ pub enum Blizzard {
     West,
 }
 
-#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub enum GridPosition {
     Empty,
     Wall,
     Blizzards(VecDeque<Blizzard>),
 }
 
-#[derive(Clone, PartialEq)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct Valley {
     map: Vec<Vec<GridPosition>>,
     blizzard_cycle_length: Option<usize>,
@@ -390,6 +392,46 @@ pub fn worth_visiting(
     true
 }
 
+#[cfg(feature = "bench")]
+pub mod benchmarks {
+    use super::*;
+    use crate::benchmarks::InputGenerator;
+    use criterion::{black_box, BenchmarkId, Criterion};
+
+    pub fn register(c: &mut Criterion) {
+        let mut generator = InputGenerator::new(24);
+        let mut group = c.benchmark_group("day24/predict_state");
+        for (width, height) in [(10, 4), (40, 10), (160, 40)] {
+            let valley = Valley::from_str(&valley_map(&mut generator, width, height));
+            group.bench_with_input(
+                BenchmarkId::from_parameter(format!("{}x{}", width, height)),
+                &valley,
+                |b, valley| b.iter(|| valley.predict_state(black_box(1))),
+            );
+        }
+        group.finish();
+    }
+
+    // A valley about half full of blizzards. Like the real inputs, no blizzard goes up or down the entrance or exit
+    // columns, as it would blow straight out of the valley.
+    fn valley_map(generator: &mut InputGenerator, width: usize, height: usize) -> String {
+        let mut map = format!("#.{}\n", "#".repeat(width));
+        for _ in 0..height {
+            map.push('#');
+            for x in 1..=width {
+                map.push(if x == 1 || x == width {
+                    generator.choose(&['.', '.', '<', '>'])
+                } else {
+                    generator.choose(&['.', '.', '.', '.', '<', '>', '^', 'v'])
+                });
+            }
+            map.push_str("#\n");
+        }
+        map.push_str(&format!("{}.#", "#".repeat(width)));
+        map
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -467,4 +509,22 @@ mod tests {
             )
         )
     }
+
+    #[test]
+    fn check_day24_state_round_trip() {
+        let mut valley = Valley::from_str(
+            "#.######
+#>>.<^<#
+#.<..<<#
+#>v.><>#
+#<^v^^>#
+######.#",
+        );
+        valley.find_blizzard_cycles();
+        let json = crate::state::to_json(&valley).unwrap();
+        let mut resumed: Valley = crate::state::from_json(&json).unwrap();
+        assert!(resumed == valley);
+        let (entrance, exit) = (resumed.get_entrance(), resumed.get_exit());
+        assert_eq!(fastest_traverse(&mut resumed, entrance, exit, false), 18);
+    }
 }
@@ -0,0 +1,98 @@
+mod day00;
+mod day01;
+mod day02;
+mod day03;
+mod day04;
+mod day05;
+mod day06;
+mod day07;
+mod day08;
+mod day09;
+mod day10;
+mod day11;
+mod day12;
+mod day13;
+mod day14;
+mod day15;
+mod day16;
+mod day17;
+mod day18;
+mod day19;
+mod day20;
+mod day21;
+mod day22;
+mod day23;
+mod day24;
+mod day25;
+
+use crate::{DayFunction, ReportFunction, SimulationFunction};
+
+// One day per line, so `cargo run -- new` can add to the list.
+#[rustfmt::skip]
+pub const DAYS: &[(usize, DayFunction)] = &[
+    (0, day00::day00),
+    (1, day01::day01),
+    (2, day02::day02),
+    (3, day03::day03),
+    (4, day04::day04),
+    (5, day05::day05),
+    (6, day06::day06),
+    (7, day07::day07),
+    (8, day08::day08),
+    (9, day09::day09),
+    (10, day10::day10),
+    (11, day11::day11),
+    (12, day12::day12),
+    (13, day13::day13),
+    (14, day14::day14),
+    (15, day15::day15),
+    (16, day16::day16),
+    (17, day17::day17),
+    (18, day18::day18),
+    (19, day19::day19),
+    (20, day20::day20),
+    (21, day21::day21),
+    (22, day22::day22),
+    (23, day23::day23),
+    (24, day24::day24),
+    (25, day25::day25),
+];
+
+// Days that can be stepped through with `cargo run -- debug <day>`.
+pub const SIMULATIONS: &[(usize, SimulationFunction)] = &[
+    (5, day05::day05_simulation),
+    (10, day10::day10_simulation),
+    (11, day11::day11_simulation),
+    (14, day14::day14_simulation),
+    (17, day17::day17_simulation),
+    (20, day20::day20_simulation),
+    (22, day22::day22_simulation),
+    (23, day23::day23_simulation),
+];
+
+// Days with extra analysis of their input, shown with `cargo run -- report <day> [options]`.
+#[rustfmt::skip]
+pub const REPORTS: &[(usize, ReportFunction)] = &[
+    (1, day01::day01_report),
+    (2, day02::day02_report),
+    (3, day03::day03_report),
+    (4, day04::day04_report),
+    (5, day05::day05_report),
+    (7, day07::day07_report),
+    (8, day08::day08_report),
+    (9, day09::day09_report),
+    (10, day10::day10_report),
+    (11, day11::day11_report),
+];
+
+// Benchmarks of the slower days' inner loops, run with `cargo run --release --features bench -- bench [filter]`.
+#[cfg(feature = "bench")]
+pub const BENCHMARKS: &[(usize, crate::benchmarks::BenchmarkFunction)] = &[
+    (15, day15::benchmarks::register),
+    (16, day16::benchmarks::register),
+    (17, day17::benchmarks::register),
+    (19, day19::benchmarks::register),
+    (20, day20::benchmarks::register),
+    (23, day23::benchmarks::register),
+    (24, day24::benchmarks::register),
+];